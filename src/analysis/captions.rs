@@ -0,0 +1,105 @@
+use common::{mime_type, MediaTrack, Resource};
+
+/// The caption tracks of every video and audio file in the cartridge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptionReport {
+    pub media: Vec<MediaCaptions>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaCaptions {
+    pub identifier: String,
+    pub href: String,
+    pub kind: MediaKind,
+    /// Locales of the file's caption and subtitle tracks. Tracks without a
+    /// locale are listed as an empty string.
+    pub locales: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Video,
+    Audio,
+}
+
+impl MediaKind {
+    /// Recognizes media files by the MIME type of their extension.
+    pub fn from_path(path: &str) -> Option<MediaKind> {
+        match mime_type(path) {
+            mime if mime.starts_with("video/") => Some(MediaKind::Video),
+            mime if mime.starts_with("audio/") => Some(MediaKind::Audio),
+            _ => None,
+        }
+    }
+}
+
+impl CaptionReport {
+    pub fn new(resources: &[Resource], tracks: &[MediaTrack]) -> CaptionReport {
+        let mut media = Vec::new();
+        for resource in resources {
+            let href = match resource.href {
+                Some(ref href) => href,
+                None => continue,
+            };
+            if let Some(kind) = MediaKind::from_path(href) {
+                let locales = tracks
+                    .iter()
+                    .filter(|track| track.media_identifier_ref == resource.identifier && track.is_caption())
+                    .map(|track| track.locale.clone().unwrap_or_default())
+                    .collect();
+                media.push(MediaCaptions {
+                    identifier: resource.identifier.clone(),
+                    href: href.clone(),
+                    kind,
+                    locales,
+                });
+            }
+        }
+        CaptionReport { media }
+    }
+
+    /// Media files without a caption or subtitle track.
+    pub fn uncaptioned(&self) -> Vec<&MediaCaptions> {
+        self.media.iter().filter(|media| media.locales.is_empty()).collect()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.uncaptioned().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaptionReport, MediaKind};
+    use common::{ItemType, MediaTrack, Resource};
+
+    fn resource(identifier: &str, href: &str) -> Resource {
+        Resource {
+            href: Some(href.to_string()),
+            identifier: identifier.to_string(),
+            item_type: ItemType::WebContent,
+            ..Resource::default()
+        }
+    }
+
+    #[test]
+    fn reports_uncaptioned_media() {
+        let resources = vec![
+            resource("video", "web_resources/lecture.MP4"),
+            resource("audio", "web_resources/podcast.mp3"),
+            resource("image", "web_resources/diagram.png"),
+        ];
+        let mut captions = MediaTrack::new(Some("video".to_string()));
+        captions.locale = Some("en".to_string());
+        let mut chapters = MediaTrack::new(Some("audio".to_string()));
+        chapters.kind = "chapters".to_string();
+        let report = CaptionReport::new(&resources, &[captions, chapters]);
+        assert_eq!(report.media.len(), 2);
+        assert_eq!(report.media[0].kind, MediaKind::Video);
+        assert_eq!(report.media[0].locales, vec!["en"]);
+        let uncaptioned = report.uncaptioned();
+        assert_eq!(uncaptioned.len(), 1);
+        assert_eq!(uncaptioned[0].href, "web_resources/podcast.mp3");
+        assert!(!report.is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+use common::{FileEntry, FileTree, Module, ModuleItem, Resource};
+
+/// A module item that points at a file students can't open.
+#[derive(Debug, Clone)]
+pub struct RestrictedFileLink<'a> {
+    pub module: &'a Module,
+    pub item: &'a ModuleItem,
+    pub file: &'a FileEntry,
+}
+
+/// Module items whose file is locked or hidden, itself or through its
+/// folder, or only available between dates.
+pub fn restricted_file_links<'a>(modules: &'a [Module], resources: &[Resource], files: &'a FileTree)
+                                 -> Vec<RestrictedFileLink<'a>> {
+    let restricted = files.restricted_files();
+    let mut links = Vec::new();
+    for module in modules {
+        for item in &module.items {
+            let href = resources
+                .iter()
+                .find(|resource| resource.identifier == item.identifier_ref)
+                .and_then(|resource| resource.href.as_ref());
+            let path = href.and_then(|href| files.file(href)).map(|file| &file.path);
+            let file = path.and_then(|path| restricted.iter().find(|file| &file.path == path));
+            if let Some(&file) = file {
+                links.push(RestrictedFileLink { module, item, file });
+            }
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::restricted_file_links;
+    use common::{FileEntry, FileTree, ItemType, Module, ModuleItem, Resource};
+
+    #[test]
+    fn finds_module_items_linking_restricted_files() {
+        let resource = |identifier: &str, href: &str| Resource {
+            href: Some(href.to_string()),
+            identifier: identifier.to_string(),
+            item_type: ItemType::WebContent,
+            ..Resource::default()
+        };
+        let resources = vec![resource("r1", "web_resources/open.pdf"), resource("r2", "web_resources/drafts/a%2Epdf")];
+        let mut files = FileTree::new();
+        files.insert(FileEntry::new("web_resources/open.pdf"));
+        files.insert(FileEntry::new("web_resources/Drafts/a.pdf"));
+        files.folder_mut("Drafts").unwrap().hidden = true;
+        let item = |identifier_ref: &str| {
+            ModuleItem::new(format!("item_{}", identifier_ref), identifier_ref.to_string(), "File".to_string(),
+                            ItemType::WebContent)
+        };
+        let modules = vec![Module::new("m1".to_string(), "Week 1".to_string(), vec![item("r1"), item("r2")])];
+
+        let links = restricted_file_links(&modules, &resources, &files);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].module.identifier, "m1");
+        assert_eq!(links[0].item.identifier_ref, "r2");
+        assert_eq!(links[0].file.path, "web_resources/Drafts/a.pdf");
+    }
+}
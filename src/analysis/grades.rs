@@ -0,0 +1,264 @@
+use common::{Assignment, AssignmentGroup};
+use std::collections::HashMap;
+
+/// Computes group and final grades the way Canvas does, from scores keyed by
+/// assignment identifier.
+#[derive(Debug)]
+pub struct GradeCalculator<'a> {
+    groups: &'a [AssignmentGroup],
+    assignments: &'a [Assignment],
+    weighted: bool,
+    ungraded: UngradedPolicy,
+}
+
+/// What to do with assignments that have no score yet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UngradedPolicy {
+    /// Leave them out, like Canvas' current grade.
+    Ignore,
+    /// Score them as zero, like Canvas' final grade.
+    CountAsZero,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupGrade {
+    pub identifier: String,
+    pub title: String,
+    pub weight: f64,
+    pub score: f64,
+    pub possible: f64,
+    /// Assignments removed by the group's drop rules.
+    pub dropped: Vec<String>,
+}
+
+impl GroupGrade {
+    /// The group grade as a percentage, if anything in it is worth points.
+    pub fn percent(&self) -> Option<f64> {
+        if self.possible > 0.0 {
+            Some(self.score / self.possible * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeReport {
+    pub groups: Vec<GroupGrade>,
+    /// The final grade as a percentage, if anything counted toward it.
+    pub final_percent: Option<f64>,
+}
+
+impl<'a> GradeCalculator<'a> {
+    /// `weighted` is true for courses whose `group_weighting_scheme` is
+    /// `percent`; otherwise every point counts the same.
+    pub fn new(groups: &'a [AssignmentGroup], assignments: &'a [Assignment], weighted: bool) -> GradeCalculator<'a> {
+        GradeCalculator {
+            groups,
+            assignments,
+            weighted,
+            ungraded: UngradedPolicy::Ignore,
+        }
+    }
+
+    pub fn ungraded(mut self, policy: UngradedPolicy) -> GradeCalculator<'a> {
+        self.ungraded = policy;
+        self
+    }
+
+    pub fn calculate(&self, scores: &HashMap<String, f64>) -> GradeReport {
+        let groups: Vec<GroupGrade> = self.groups.iter().map(|group| self.group_grade(group, scores)).collect();
+        let final_percent = if self.weighted {
+            weighted_percent(&groups)
+        } else {
+            let score: f64 = groups.iter().map(|group| group.score).sum();
+            let possible: f64 = groups.iter().map(|group| group.possible).sum();
+            if possible > 0.0 {
+                Some(score / possible * 100.0)
+            } else {
+                None
+            }
+        };
+        GradeReport { groups, final_percent }
+    }
+
+    fn group_grade(&self, group: &AssignmentGroup, scores: &HashMap<String, f64>) -> GroupGrade {
+        let mut graded: Vec<(&str, f64, f64)> = self.assignments
+            .iter()
+            .filter(|assignment| {
+                assignment.counts_toward_grade()
+                    && assignment.assignment_group_identifier_ref.as_deref() == Some(group.identifier.as_str())
+            })
+            .filter_map(|assignment| {
+                let score = match (scores.get(&assignment.identifier), self.ungraded) {
+                    (Some(&score), _) => score,
+                    (None, UngradedPolicy::CountAsZero) => 0.0,
+                    (None, UngradedPolicy::Ignore) => return None,
+                };
+                Some((assignment.identifier.as_str(), score, assignment.points_possible.unwrap_or(0.0)))
+            })
+            .collect();
+        let mut dropped = Vec::new();
+        for _ in 0..group.drop_lowest {
+            match drop_candidate(&graded, group, true) {
+                Some(index) => dropped.push(graded.remove(index).0.to_string()),
+                None => break,
+            }
+        }
+        for _ in 0..group.drop_highest {
+            match drop_candidate(&graded, group, false) {
+                Some(index) => dropped.push(graded.remove(index).0.to_string()),
+                None => break,
+            }
+        }
+        GroupGrade {
+            identifier: group.identifier.clone(),
+            title: group.title.clone(),
+            weight: group.group_weight,
+            score: graded.iter().map(|&(_, score, _)| score).sum(),
+            possible: graded.iter().map(|&(_, _, possible)| possible).sum(),
+            dropped,
+        }
+    }
+}
+
+/// The assignment whose removal leaves the best group grade when dropping
+/// the lowest, or the worst when dropping the highest. At least one
+/// assignment is always kept, and `never_drop` ones are never candidates.
+fn drop_candidate(graded: &[(&str, f64, f64)], group: &AssignmentGroup, lowest: bool) -> Option<usize> {
+    if graded.len() < 2 {
+        return None;
+    }
+    let score: f64 = graded.iter().map(|&(_, score, _)| score).sum();
+    let possible: f64 = graded.iter().map(|&(_, _, possible)| possible).sum();
+    let remaining = |&(_, s, p): &(&str, f64, f64)| {
+        if possible - p > 0.0 {
+            (score - s) / (possible - p)
+        } else {
+            0.0
+        }
+    };
+    let candidates = graded
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(identifier, _, _))| !group.never_drop.iter().any(|never| never == identifier));
+    let mut best: Option<(usize, f64)> = None;
+    for (index, entry) in candidates {
+        let grade = remaining(entry);
+        let better = match best {
+            None => true,
+            Some((_, best_grade)) if lowest => grade > best_grade,
+            Some((_, best_grade)) => grade < best_grade,
+        };
+        if better {
+            best = Some((index, grade));
+        }
+    }
+    best.map(|(index, _)| index)
+}
+
+/// Weights of groups with nothing to grade are left out; when the rest add
+/// up to less than 100 they are scaled up, as Canvas does.
+fn weighted_percent(groups: &[GroupGrade]) -> Option<f64> {
+    let graded: Vec<(f64, f64)> = groups
+        .iter()
+        .filter_map(|group| group.percent().map(|percent| (percent, group.weight)))
+        .collect();
+    let total_weight: f64 = graded.iter().map(|&(_, weight)| weight).sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+    let weighted: f64 = graded.iter().map(|&(percent, weight)| percent * weight).sum();
+    Some(weighted / total_weight.min(100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GradeCalculator, UngradedPolicy};
+    use common::{Assignment, AssignmentGroup};
+    use std::collections::HashMap;
+
+    fn group(identifier: &str, weight: f64) -> AssignmentGroup {
+        let mut group = AssignmentGroup::new(Some(identifier.to_string()));
+        group.group_weight = weight;
+        group
+    }
+
+    fn assignment(identifier: &str, group: &str, points: f64) -> Assignment {
+        let mut assignment = Assignment::new(Some(identifier.to_string()));
+        assignment.assignment_group_identifier_ref = Some(group.to_string());
+        assignment.points_possible = Some(points);
+        assignment
+    }
+
+    fn scores(scores: &[(&str, f64)]) -> HashMap<String, f64> {
+        scores.iter().map(|&(identifier, score)| (identifier.to_string(), score)).collect()
+    }
+
+    fn fixture() -> (Vec<AssignmentGroup>, Vec<Assignment>) {
+        let groups = vec![group("homework", 40.0), group("exams", 60.0)];
+        let assignments = vec![
+            assignment("hw1", "homework", 10.0),
+            assignment("hw2", "homework", 10.0),
+            assignment("hw3", "homework", 10.0),
+            assignment("midterm", "exams", 100.0),
+            assignment("final", "exams", 100.0),
+        ];
+        (groups, assignments)
+    }
+
+    #[test]
+    fn totals_points_when_unweighted() {
+        let (groups, assignments) = fixture();
+        let report = GradeCalculator::new(&groups, &assignments, false)
+            .calculate(&scores(&[("hw1", 10.0), ("hw2", 5.0), ("midterm", 75.0)]));
+        assert_eq!(report.groups[0].percent(), Some(75.0));
+        assert_eq!(report.groups[1].percent(), Some(75.0));
+        assert_eq!(report.final_percent, Some(75.0));
+    }
+
+    #[test]
+    fn weights_groups() {
+        let (groups, assignments) = fixture();
+        let scores = scores(&[("hw1", 10.0), ("hw2", 10.0), ("hw3", 10.0), ("midterm", 50.0), ("final", 50.0)]);
+        let report = GradeCalculator::new(&groups, &assignments, true).calculate(&scores);
+        assert_eq!(report.final_percent, Some(70.0));
+    }
+
+    #[test]
+    fn rescales_weights_of_ungraded_groups() {
+        let (groups, assignments) = fixture();
+        let calculator = GradeCalculator::new(&groups, &assignments, true);
+        let report = calculator.calculate(&scores(&[("hw1", 8.0)]));
+        assert_eq!(report.groups[1].percent(), None);
+        assert_eq!(report.final_percent, Some(80.0));
+        let report = calculator.ungraded(UngradedPolicy::CountAsZero).calculate(&scores(&[("hw1", 9.0)]));
+        assert_eq!(report.final_percent, Some(12.0));
+    }
+
+    #[test]
+    fn applies_drop_rules() {
+        let (mut groups, assignments) = fixture();
+        groups[0].drop_lowest = 1;
+        groups[0].never_drop = vec!["hw1".to_string()];
+        groups[1].drop_highest = 1;
+        let scores = scores(&[("hw1", 2.0), ("hw2", 6.0), ("hw3", 8.0), ("midterm", 90.0), ("final", 70.0)]);
+        let report = GradeCalculator::new(&groups, &assignments, true).calculate(&scores);
+        assert_eq!(report.groups[0].dropped, vec!["hw2"]);
+        assert_eq!(report.groups[0].percent(), Some(50.0));
+        assert_eq!(report.groups[1].dropped, vec!["midterm"]);
+        assert_eq!(report.final_percent, Some(62.0));
+    }
+
+    #[test]
+    fn skips_assignments_that_do_not_count() {
+        let (groups, mut assignments) = fixture();
+        assignments[1].workflow_state = Some("unpublished".to_string());
+        assignments[2].omit_from_final_grade = true;
+        let report = GradeCalculator::new(&groups, &assignments, false)
+            .ungraded(UngradedPolicy::CountAsZero)
+            .calculate(&scores(&[("hw1", 7.0)]));
+        assert_eq!(report.groups[0].possible, 10.0);
+        assert_eq!(report.groups[0].percent(), Some(70.0));
+    }
+}
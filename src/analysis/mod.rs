@@ -0,0 +1,13 @@
+pub mod captions;
+pub mod files;
+pub mod grades;
+pub mod outcomes;
+pub mod prerequisites;
+pub mod timeline;
+
+pub use self::captions::{CaptionReport, MediaCaptions, MediaKind};
+pub use self::files::{restricted_file_links, RestrictedFileLink};
+pub use self::grades::{GradeCalculator, GradeReport, GroupGrade, UngradedPolicy};
+pub use self::outcomes::{AlignmentGap, AlignmentReport, OutcomeCoverage};
+pub use self::prerequisites::{LockReason, LockedModule, PrerequisiteGraph, PrerequisiteReport, RequirementProblem};
+pub use self::timeline::{Timeline, TimelineEntry, TimelineKind};
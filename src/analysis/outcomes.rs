@@ -0,0 +1,235 @@
+use common::{Assignment, Outcome, OutcomeGroup, QuestionBank, Rubric};
+
+/// For each outcome, the content that assesses it, directly or through a
+/// rubric or question bank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignmentReport {
+    pub outcomes: Vec<OutcomeCoverage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutcomeCoverage {
+    pub identifier: String,
+    pub title: String,
+    pub assignments: Vec<String>,
+    pub rubrics: Vec<String>,
+    pub quizzes: Vec<String>,
+    pub question_banks: Vec<String>,
+    pub gaps: Vec<AlignmentGap>,
+}
+
+/// Why an outcome may not be measured in the course.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlignmentGap {
+    /// Nothing in the course assesses the outcome.
+    NotAssessed,
+    /// An alignment names content that is not in the cartridge.
+    MissingContent { content_type: String, content_id: String },
+    /// The aligned rubric is not attached to any assignment.
+    UnusedRubric { rubric: String },
+    /// No quiz draws questions from the aligned bank.
+    UnusedQuestionBank { bank: String },
+    /// Every assignment that assesses the outcome is unpublished.
+    NoPublishedAssignment,
+}
+
+impl AlignmentReport {
+    pub fn new(outcomes: &OutcomeGroup, assignments: &[Assignment], rubrics: &[Rubric],
+               question_banks: &[QuestionBank]) -> AlignmentReport {
+        AlignmentReport {
+            outcomes: outcomes
+                .all_outcomes()
+                .into_iter()
+                .map(|outcome| coverage(outcome, assignments, rubrics, question_banks))
+                .collect(),
+        }
+    }
+
+    /// Outcomes with at least one gap.
+    pub fn gaps(&self) -> Vec<&OutcomeCoverage> {
+        self.outcomes.iter().filter(|outcome| !outcome.gaps.is_empty()).collect()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.gaps.is_empty())
+    }
+}
+
+fn coverage(outcome: &Outcome, assignments: &[Assignment], rubrics: &[Rubric],
+            question_banks: &[QuestionBank]) -> OutcomeCoverage {
+    let mut coverage = OutcomeCoverage {
+        identifier: outcome.identifier.clone(),
+        title: outcome.title.clone(),
+        assignments: Vec::new(),
+        rubrics: Vec::new(),
+        quizzes: Vec::new(),
+        question_banks: Vec::new(),
+        gaps: Vec::new(),
+    };
+    for alignment in &outcome.alignments {
+        let id = &alignment.content_id;
+        let (found, aligned) = match alignment.content_type.as_str() {
+            "Rubric" => (rubrics.iter().any(|rubric| &rubric.identifier == id), &mut coverage.rubrics),
+            "Assignment" => {
+                (assignments.iter().any(|assignment| &assignment.identifier == id), &mut coverage.assignments)
+            }
+            "AssessmentQuestionBank" => {
+                (question_banks.iter().any(|bank| &bank.identifier == id), &mut coverage.question_banks)
+            }
+            _ => continue,
+        };
+        if found {
+            push_new(aligned, id);
+        } else {
+            coverage.gaps.push(AlignmentGap::MissingContent {
+                content_type: alignment.content_type.clone(),
+                content_id: id.clone(),
+            });
+        }
+    }
+    let criterion_rubrics = rubrics.iter().filter(|rubric| {
+        rubric.criteria.iter().any(|criterion| {
+            criterion.learning_outcome_identifier_ref.as_deref() == Some(outcome.identifier.as_str())
+        })
+    });
+    for rubric in criterion_rubrics {
+        push_new(&mut coverage.rubrics, &rubric.identifier);
+    }
+    for rubric in &coverage.rubrics {
+        let graded: Vec<&Assignment> = assignments
+            .iter()
+            .filter(|assignment| assignment.rubric_identifier_ref.as_deref() == Some(rubric.as_str()))
+            .collect();
+        if graded.is_empty() {
+            coverage.gaps.push(AlignmentGap::UnusedRubric { rubric: rubric.clone() });
+        }
+        for assignment in graded {
+            push_new(&mut coverage.assignments, &assignment.identifier);
+        }
+    }
+    for bank in question_banks {
+        if !coverage.question_banks.contains(&bank.identifier) {
+            continue;
+        }
+        if bank.quiz_identifiers.is_empty() {
+            coverage.gaps.push(AlignmentGap::UnusedQuestionBank { bank: bank.identifier.clone() });
+        }
+        for quiz in &bank.quiz_identifiers {
+            push_new(&mut coverage.quizzes, quiz);
+        }
+    }
+    let assessing: Vec<&Assignment> = assignments
+        .iter()
+        .filter(|assignment| coverage.assignments.contains(&assignment.identifier))
+        .collect();
+    for assignment in &assessing {
+        if let Some(ref quiz) = assignment.quiz_identifier_ref {
+            push_new(&mut coverage.quizzes, quiz);
+        }
+    }
+    if coverage.assignments.is_empty() && coverage.quizzes.is_empty() {
+        coverage.gaps.insert(0, AlignmentGap::NotAssessed);
+    } else if !assessing.is_empty() && !assessing.iter().any(|assignment| assignment.published()) {
+        coverage.gaps.push(AlignmentGap::NoPublishedAssignment);
+    }
+    coverage
+}
+
+fn push_new(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|existing| existing == value) {
+        values.push(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlignmentGap, AlignmentReport};
+    use common::{Assignment, Outcome, OutcomeAlignment, OutcomeGroup, QuestionBank, Rubric, RubricCriterion};
+
+    fn outcome(identifier: &str, alignments: &[(&str, &str)]) -> Outcome {
+        let mut outcome = Outcome::new(Some(identifier.to_string()));
+        outcome.alignments = alignments
+            .iter()
+            .map(|&(content_type, content_id)| OutcomeAlignment {
+                content_type: content_type.to_string(),
+                content_id: content_id.to_string(),
+                ..OutcomeAlignment::default()
+            })
+            .collect();
+        outcome
+    }
+
+    fn rubric(identifier: &str, outcome: Option<&str>) -> Rubric {
+        let mut rubric = Rubric::new(Some(identifier.to_string()));
+        rubric.criteria.push(RubricCriterion {
+            learning_outcome_identifier_ref: outcome.map(str::to_string),
+            ..RubricCriterion::default()
+        });
+        rubric
+    }
+
+    fn assignment(identifier: &str, rubric: Option<&str>) -> Assignment {
+        let mut assignment = Assignment::new(Some(identifier.to_string()));
+        assignment.rubric_identifier_ref = rubric.map(str::to_string);
+        assignment
+    }
+
+    fn report(outcomes: Vec<Outcome>) -> AlignmentReport {
+        let group = OutcomeGroup { outcomes, ..OutcomeGroup::default() };
+        let mut quiz = assignment("quiz_assignment", None);
+        quiz.quiz_identifier_ref = Some("quiz".to_string());
+        let mut unpublished = assignment("draft", None);
+        unpublished.workflow_state = Some("unpublished".to_string());
+        let assignments = vec![assignment("essay", Some("essay_rubric")), quiz, unpublished];
+        let rubrics = vec![rubric("essay_rubric", Some("writing")), rubric("spare_rubric", None)];
+        let banks = vec![
+            QuestionBank {
+                identifier: "bank".to_string(),
+                title: "Bank".to_string(),
+                quiz_identifiers: vec!["other_quiz".to_string()],
+            },
+            QuestionBank { identifier: "empty_bank".to_string(), ..QuestionBank::default() },
+        ];
+        AlignmentReport::new(&group, &assignments, &rubrics, &banks)
+    }
+
+    #[test]
+    fn follows_rubrics_to_assignments() {
+        let report = report(vec![outcome("writing", &[])]);
+        let writing = &report.outcomes[0];
+        assert_eq!(writing.rubrics, vec!["essay_rubric"]);
+        assert_eq!(writing.assignments, vec!["essay"]);
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn follows_assignments_and_banks_to_quizzes() {
+        let report = report(vec![outcome("math", &[("Assignment", "quiz_assignment"), ("AssessmentQuestionBank", "bank")])]);
+        let math = &report.outcomes[0];
+        assert_eq!(math.question_banks, vec!["bank"]);
+        assert_eq!(math.quizzes, vec!["other_quiz", "quiz"]);
+        assert!(math.gaps.is_empty());
+    }
+
+    #[test]
+    fn flags_gaps() {
+        let report = report(vec![
+            outcome("unassessed", &[]),
+            outcome("missing", &[("Rubric", "gone")]),
+            outcome("unused", &[("Rubric", "spare_rubric"), ("AssessmentQuestionBank", "empty_bank")]),
+            outcome("draft", &[("Assignment", "draft")]),
+        ]);
+        assert_eq!(report.gaps().len(), 4);
+        assert_eq!(report.outcomes[0].gaps, vec![AlignmentGap::NotAssessed]);
+        assert_eq!(report.outcomes[1].gaps, vec![
+            AlignmentGap::NotAssessed,
+            AlignmentGap::MissingContent { content_type: "Rubric".to_string(), content_id: "gone".to_string() },
+        ]);
+        assert_eq!(report.outcomes[2].gaps, vec![
+            AlignmentGap::NotAssessed,
+            AlignmentGap::UnusedRubric { rubric: "spare_rubric".to_string() },
+            AlignmentGap::UnusedQuestionBank { bank: "empty_bank".to_string() },
+        ]);
+        assert_eq!(report.outcomes[3].gaps, vec![AlignmentGap::NoPublishedAssignment]);
+    }
+}
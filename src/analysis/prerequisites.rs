@@ -0,0 +1,396 @@
+use common::{Module, ModuleItem};
+use std::collections::HashMap;
+
+/// The module prerequisite graph of a course. Edges run from a prerequisite
+/// to the module it unlocks.
+#[derive(Debug)]
+pub struct PrerequisiteGraph<'a> {
+    modules: &'a [Module],
+    edges: Vec<(usize, usize)>,
+    missing: Vec<(usize, String)>,
+}
+
+/// Why a module can never be unlocked by a student.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LockReason {
+    /// The prerequisite does not name any module in the course.
+    MissingPrerequisite { identifier_ref: String },
+    /// The module depends, directly or not, on itself.
+    Cycle,
+    /// The prerequisite module can never be unlocked itself.
+    LockedPrerequisite { identifier: String },
+    /// The prerequisite module is unlocked but can never be completed.
+    IncompletePrerequisite { identifier: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedModule {
+    pub identifier: String,
+    pub title: String,
+    pub reasons: Vec<LockReason>,
+}
+
+/// A completion requirement students cannot meet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequirementProblem {
+    MissingItem { module: String, identifier_ref: String },
+    UnpublishedItem { module: String, item: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrerequisiteReport {
+    pub cycles: Vec<Vec<String>>,
+    pub locked_modules: Vec<LockedModule>,
+    pub requirement_problems: Vec<RequirementProblem>,
+}
+
+impl PrerequisiteReport {
+    pub fn is_ok(&self) -> bool {
+        self.cycles.is_empty() && self.locked_modules.is_empty() && self.requirement_problems.is_empty()
+    }
+}
+
+impl<'a> PrerequisiteGraph<'a> {
+    pub fn new(modules: &'a [Module]) -> PrerequisiteGraph<'a> {
+        let index: HashMap<&str, usize> = modules
+            .iter()
+            .enumerate()
+            .map(|(i, module)| (module.identifier.as_str(), i))
+            .collect();
+        let mut edges = Vec::new();
+        let mut missing = Vec::new();
+        for (to, module) in modules.iter().enumerate() {
+            for prerequisite in &module.prerequisites {
+                match index.get(prerequisite.identifier_ref.as_str()) {
+                    Some(&from) => edges.push((from, to)),
+                    None => missing.push((to, prerequisite.identifier_ref.clone())),
+                }
+            }
+        }
+        PrerequisiteGraph { modules, edges, missing }
+    }
+
+    /// Modules that must be completed before `module` unlocks.
+    pub fn prerequisites_of(&self, module: &str) -> Vec<&'a Module> {
+        self.edges
+            .iter()
+            .filter(|&&(_, to)| self.modules[to].identifier == module)
+            .map(|&(from, _)| &self.modules[from])
+            .collect()
+    }
+
+    /// Each prerequisite cycle as the identifiers of the modules in it, in
+    /// course order.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        self.strongly_connected()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.edges.contains(&(component[0], component[0]))
+            })
+            .map(|component| component.iter().map(|&i| self.modules[i].identifier.clone()).collect())
+            .collect()
+    }
+
+    /// Completion requirements that point at items that are not in the
+    /// module, or at items students never see.
+    pub fn requirement_problems(&self) -> Vec<RequirementProblem> {
+        let mut problems = Vec::new();
+        for module in self.modules {
+            for requirement in &module.completion_requirements {
+                match find_item(module, &requirement.identifier_ref) {
+                    None => problems.push(RequirementProblem::MissingItem {
+                        module: module.identifier.clone(),
+                        identifier_ref: requirement.identifier_ref.clone(),
+                    }),
+                    Some(item) if !item.published => problems.push(RequirementProblem::UnpublishedItem {
+                        module: module.identifier.clone(),
+                        item: item.identifier.clone(),
+                    }),
+                    Some(_) => {}
+                }
+            }
+        }
+        problems
+    }
+
+    /// Modules a student can never unlock, with every reason that applies.
+    pub fn locked_modules(&self) -> Vec<LockedModule> {
+        let in_cycle: Vec<bool> = {
+            let mut flags = vec![false; self.modules.len()];
+            for component in self.strongly_connected() {
+                if component.len() > 1 || self.edges.contains(&(component[0], component[0])) {
+                    for i in component {
+                        flags[i] = true;
+                    }
+                }
+            }
+            flags
+        };
+        let mut unlockable: Vec<Option<bool>> = vec![None; self.modules.len()];
+        (0..self.modules.len())
+            .filter_map(|i| {
+                let reasons = self.lock_reasons(i, &in_cycle, &mut unlockable);
+                if reasons.is_empty() {
+                    None
+                } else {
+                    Some(LockedModule {
+                        identifier: self.modules[i].identifier.clone(),
+                        title: self.modules[i].title.clone(),
+                        reasons,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    pub fn report(&self) -> PrerequisiteReport {
+        PrerequisiteReport {
+            cycles: self.cycles(),
+            locked_modules: self.locked_modules(),
+            requirement_problems: self.requirement_problems(),
+        }
+    }
+
+    /// Graphviz DOT source for the graph. Modules that can never unlock are
+    /// filled, cycle edges are red and missing prerequisites are dashed.
+    pub fn to_dot(&self) -> String {
+        let locked: Vec<String> = self.locked_modules().into_iter().map(|module| module.identifier).collect();
+        let cycles = self.cycles();
+        let in_same_cycle = |a: &str, b: &str| {
+            cycles.iter().any(|cycle| cycle.iter().any(|id| id == a) && cycle.iter().any(|id| id == b))
+        };
+        let mut dot = String::from("digraph prerequisites {\n    rankdir=LR;\n    node [shape=box];\n");
+        for module in self.modules {
+            let style = if locked.contains(&module.identifier) {
+                ", style=filled, fillcolor=\"#f4cccc\""
+            } else if !module.published {
+                ", style=dotted"
+            } else {
+                ""
+            };
+            dot.push_str(&format!("    {} [label={}{}];\n",
+                                  dot_quote(&module.identifier), dot_quote(&module.title), style));
+        }
+        for &(from, to) in &self.edges {
+            let (from, to) = (&self.modules[from].identifier, &self.modules[to].identifier);
+            let style = if in_same_cycle(from, to) { " [color=red]" } else { "" };
+            dot.push_str(&format!("    {} -> {}{};\n", dot_quote(from), dot_quote(to), style));
+        }
+        for (to, identifier_ref) in &self.missing {
+            dot.push_str(&format!("    {} [label=\"missing\", style=dashed];\n", dot_quote(identifier_ref)));
+            dot.push_str(&format!("    {} -> {} [style=dashed];\n",
+                                  dot_quote(identifier_ref), dot_quote(&self.modules[*to].identifier)));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// A module can be completed when students can see it and every
+    /// completion requirement names a published item in it.
+    fn completable(&self, i: usize) -> bool {
+        let module = &self.modules[i];
+        module.published && module.completion_requirements.iter().all(|requirement| {
+            find_item(module, &requirement.identifier_ref).is_some_and(|item| item.published)
+        })
+    }
+
+    fn lock_reasons(&self, i: usize, in_cycle: &[bool], unlockable: &mut Vec<Option<bool>>) -> Vec<LockReason> {
+        let mut reasons: Vec<LockReason> = self.missing
+            .iter()
+            .filter(|&&(to, _)| to == i)
+            .map(|(_, identifier_ref)| LockReason::MissingPrerequisite { identifier_ref: identifier_ref.clone() })
+            .collect();
+        if in_cycle[i] {
+            reasons.push(LockReason::Cycle);
+        }
+        for &(from, to) in &self.edges {
+            if to != i || (in_cycle[from] && in_cycle[i]) {
+                continue;
+            }
+            let identifier = self.modules[from].identifier.clone();
+            if !self.can_unlock(from, in_cycle, unlockable) {
+                reasons.push(LockReason::LockedPrerequisite { identifier });
+            } else if !self.completable(from) {
+                reasons.push(LockReason::IncompletePrerequisite { identifier });
+            }
+        }
+        unlockable[i] = Some(reasons.is_empty());
+        reasons
+    }
+
+    fn can_unlock(&self, i: usize, in_cycle: &[bool], unlockable: &mut Vec<Option<bool>>) -> bool {
+        if let Some(result) = unlockable[i] {
+            return result;
+        }
+        if in_cycle[i] {
+            unlockable[i] = Some(false);
+            return false;
+        }
+        self.lock_reasons(i, in_cycle, unlockable).is_empty()
+    }
+
+    /// Tarjan's algorithm; components come back in course order.
+    fn strongly_connected(&self) -> Vec<Vec<usize>> {
+        let count = self.modules.len();
+        let mut state = Tarjan {
+            index: vec![None; count],
+            low: vec![0; count],
+            on_stack: vec![false; count],
+            stack: Vec::new(),
+            next: 0,
+            components: Vec::new(),
+        };
+        for i in 0..count {
+            if state.index[i].is_none() {
+                self.visit(i, &mut state);
+            }
+        }
+        let mut components = state.components;
+        for component in components.iter_mut() {
+            component.sort();
+        }
+        components.sort();
+        components
+    }
+
+    fn visit(&self, i: usize, state: &mut Tarjan) {
+        state.index[i] = Some(state.next);
+        state.low[i] = state.next;
+        state.next += 1;
+        state.stack.push(i);
+        state.on_stack[i] = true;
+        for &(from, to) in &self.edges {
+            if from != i {
+                continue;
+            }
+            match state.index[to] {
+                None => {
+                    self.visit(to, state);
+                    state.low[i] = state.low[i].min(state.low[to]);
+                }
+                Some(index) if state.on_stack[to] => state.low[i] = state.low[i].min(index),
+                Some(_) => {}
+            }
+        }
+        if state.index[i] == Some(state.low[i]) {
+            let mut component = Vec::new();
+            while let Some(j) = state.stack.pop() {
+                state.on_stack[j] = false;
+                component.push(j);
+                if j == i {
+                    break;
+                }
+            }
+            state.components.push(component);
+        }
+    }
+}
+
+struct Tarjan {
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+fn find_item<'a>(module: &'a Module, identifier: &str) -> Option<&'a ModuleItem> {
+    module.items.iter().find(|item| item.identifier == identifier)
+}
+
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LockReason, PrerequisiteGraph, RequirementProblem};
+    use common::{CompletionRequirement, CompletionRequirementType, ItemType, Module, ModuleItem,
+                 ModulePrerequisite};
+
+    fn module(identifier: &str, prerequisites: &[&str]) -> Module {
+        let mut module = Module::new(identifier.to_string(), format!("Module {}", identifier), Vec::new());
+        module.prerequisites = prerequisites
+            .iter()
+            .map(|identifier_ref| ModulePrerequisite {
+                prerequisite_type: "context_module".to_string(),
+                title: "".to_string(),
+                identifier_ref: identifier_ref.to_string(),
+            })
+            .collect();
+        module
+    }
+
+    fn require(module: &mut Module, item: &str, published: bool) {
+        let mut module_item = ModuleItem::new(item.to_string(), "".to_string(), item.to_string(), ItemType::NoType);
+        module_item.published = published;
+        module.items.push(module_item);
+        module.completion_requirements.push(CompletionRequirement {
+            requirement_type: CompletionRequirementType::MustView,
+            identifier_ref: item.to_string(),
+        });
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let modules = vec![module("a", &[]), module("b", &["c"]), module("c", &["b"]), module("d", &["d"])];
+        let graph = PrerequisiteGraph::new(&modules);
+        assert_eq!(graph.cycles(), vec![vec!["b".to_string(), "c".to_string()], vec!["d".to_string()]]);
+    }
+
+    #[test]
+    fn finds_locked_modules() {
+        let mut modules = vec![module("a", &[]), module("b", &["a"]), module("c", &["b"]), module("d", &["zz"])];
+        require(&mut modules[0], "a1", false);
+        let graph = PrerequisiteGraph::new(&modules);
+        let locked = graph.locked_modules();
+        assert_eq!(locked.len(), 3);
+        assert_eq!(locked[0].identifier, "b");
+        assert_eq!(locked[0].reasons, vec![LockReason::IncompletePrerequisite { identifier: "a".to_string() }]);
+        assert_eq!(locked[1].reasons, vec![LockReason::LockedPrerequisite { identifier: "b".to_string() }]);
+        assert_eq!(locked[2].reasons, vec![LockReason::MissingPrerequisite { identifier_ref: "zz".to_string() }]);
+    }
+
+    #[test]
+    fn modules_after_a_cycle_are_locked() {
+        let modules = vec![module("a", &["b"]), module("b", &["a"]), module("c", &["a"])];
+        let locked = PrerequisiteGraph::new(&modules).locked_modules();
+        assert_eq!(locked.len(), 3);
+        assert_eq!(locked[0].reasons, vec![LockReason::Cycle]);
+        assert_eq!(locked[2].reasons, vec![LockReason::LockedPrerequisite { identifier: "a".to_string() }]);
+    }
+
+    #[test]
+    fn finds_requirement_problems() {
+        let mut modules = vec![module("a", &[])];
+        require(&mut modules[0], "a1", false);
+        modules[0].completion_requirements.push(CompletionRequirement {
+            requirement_type: CompletionRequirementType::MustSubmit,
+            identifier_ref: "gone".to_string(),
+        });
+        let problems = PrerequisiteGraph::new(&modules).requirement_problems();
+        assert_eq!(problems, vec![
+            RequirementProblem::UnpublishedItem { module: "a".to_string(), item: "a1".to_string() },
+            RequirementProblem::MissingItem { module: "a".to_string(), identifier_ref: "gone".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn healthy_course_reports_ok() {
+        let mut modules = vec![module("a", &[]), module("b", &["a"])];
+        require(&mut modules[0], "a1", true);
+        assert!(PrerequisiteGraph::new(&modules).report().is_ok());
+    }
+
+    #[test]
+    fn emits_dot() {
+        let modules = vec![module("a", &[]), module("b", &["a", "zz"])];
+        let dot = PrerequisiteGraph::new(&modules).to_dot();
+        assert!(dot.starts_with("digraph prerequisites {"));
+        assert!(dot.contains("    \"a\" [label=\"Module a\"];\n"));
+        assert!(dot.contains("    \"a\" -> \"b\";\n"));
+        assert!(dot.contains("    \"zz\" -> \"b\" [style=dashed];\n"));
+        assert!(dot.contains("    \"b\" [label=\"Module b\", style=filled"));
+    }
+}
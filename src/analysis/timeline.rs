@@ -0,0 +1,157 @@
+use common::{DateTime, Summary};
+use export;
+
+/// Every dated thing in the course, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timeline {
+    pub entries: Vec<TimelineEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    pub at: DateTime,
+    /// Only calendar events have an end.
+    pub end: Option<DateTime>,
+    pub all_day: bool,
+    pub kind: TimelineKind,
+    /// The course, event, assignment or module the date belongs to.
+    pub identifier: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub published: bool,
+}
+
+/// Variants are in the order entries at the same time are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimelineKind {
+    CourseStart,
+    ModuleUnlock,
+    Unlock,
+    Event,
+    Due,
+    Lock,
+    CourseEnd,
+}
+
+impl TimelineKind {
+    pub fn label(&self) -> &'static str {
+        match *self {
+            TimelineKind::CourseStart => "Course starts",
+            TimelineKind::ModuleUnlock => "Module unlocks",
+            TimelineKind::Unlock => "Available",
+            TimelineKind::Event => "Event",
+            TimelineKind::Due => "Due",
+            TimelineKind::Lock => "Closes",
+            TimelineKind::CourseEnd => "Course ends",
+        }
+    }
+}
+
+impl Timeline {
+    /// Merges course dates, calendar events, assignment, quiz and graded
+    /// discussion dates, and module unlock dates. Dates that cannot be read
+    /// are left out.
+    pub fn new(summary: &Summary) -> Timeline {
+        let mut timeline = Timeline { entries: Vec::new() };
+        if let Some(ref settings) = summary.course_settings {
+            let title = &settings.title;
+            timeline.add(&settings.start_at, TimelineKind::CourseStart, &settings.identifier, title, true);
+            timeline.add(&settings.conclude_at, TimelineKind::CourseEnd, &settings.identifier, title, true);
+        }
+        for event in &summary.events {
+            let start = match event.start_at.as_deref().and_then(DateTime::parse) {
+                Some(start) => start,
+                None => continue,
+            };
+            timeline.entries.push(TimelineEntry {
+                at: start,
+                end: event.end_at.as_deref().and_then(DateTime::parse),
+                all_day: event.all_day,
+                kind: TimelineKind::Event,
+                identifier: event.identifier.clone(),
+                title: event.title.clone(),
+                description: event.description.clone(),
+                published: true,
+            });
+        }
+        for assignment in &summary.assignments {
+            let (identifier, title, published) = (&assignment.identifier, &assignment.title, assignment.published());
+            timeline.add(&assignment.unlock_at, TimelineKind::Unlock, identifier, title, published);
+            timeline.add(&assignment.due_at, TimelineKind::Due, identifier, title, published);
+            timeline.add(&assignment.lock_at, TimelineKind::Lock, identifier, title, published);
+        }
+        for module in &summary.modules {
+            timeline.add(&module.unlock_at, TimelineKind::ModuleUnlock, &module.identifier, &module.title,
+                         module.published);
+        }
+        timeline.entries.sort_by(|a, b| (a.at, a.kind, &a.title).cmp(&(b.at, b.kind, &b.title)));
+        timeline
+    }
+
+    fn add(&mut self, date: &Option<String>, kind: TimelineKind, identifier: &str, title: &str, published: bool) {
+        if let Some(at) = date.as_deref().and_then(DateTime::parse) {
+            self.entries.push(TimelineEntry {
+                at,
+                end: None,
+                all_day: false,
+                kind,
+                identifier: identifier.to_string(),
+                title: title.to_string(),
+                description: None,
+                published,
+            });
+        }
+    }
+
+    /// The timeline as an iCalendar (`.ics`) document.
+    pub fn to_ics(&self, calendar_name: &str) -> String {
+        export::ics::calendar(self, calendar_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Timeline, TimelineKind};
+    use common::{Assignment, CalendarEvent, CourseSettings, ManifestBuilder, Module, Summary};
+
+    fn summary() -> Summary {
+        let mut summary = Summary::new(ManifestBuilder::new().finalize());
+        let mut settings = CourseSettings::new(Some("course".to_string()));
+        settings.start_at = Some("2016-01-11T07:00:00".to_string());
+        settings.conclude_at = Some("2016-05-01T06:00:00".to_string());
+        summary.course_settings = Some(settings);
+        let mut event = CalendarEvent::new(Some("event".to_string()));
+        event.title = "Review".to_string();
+        event.start_at = Some("2016-03-01T18:00:00".to_string());
+        event.end_at = Some("2016-03-01T19:00:00".to_string());
+        summary.events.push(event);
+        let mut assignment = Assignment::new(Some("essay".to_string()));
+        assignment.title = "Essay".to_string();
+        assignment.unlock_at = Some("2016-02-01T07:00:00".to_string());
+        assignment.due_at = Some("2016-03-01T18:00:00".to_string());
+        assignment.lock_at = Some("not a date".to_string());
+        summary.assignments.push(assignment);
+        let mut module = Module::new("week_1".to_string(), "Week 1".to_string(), Vec::new());
+        module.unlock_at = Some("2016-01-11T07:00:00".to_string());
+        summary.modules.push(module);
+        summary
+    }
+
+    #[test]
+    fn merges_and_sorts_dates() {
+        let timeline = Timeline::new(&summary());
+        let kinds: Vec<TimelineKind> = timeline.entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, vec![
+            TimelineKind::CourseStart,
+            TimelineKind::ModuleUnlock,
+            TimelineKind::Unlock,
+            TimelineKind::Event,
+            TimelineKind::Due,
+            TimelineKind::CourseEnd,
+        ]);
+        let event = &timeline.entries[3];
+        assert_eq!(event.title, "Review");
+        assert_eq!(event.end.map(|end| end.to_string()), Some("2016-03-01T19:00:00".to_string()));
+        assert_eq!(timeline.entries[4].identifier, "essay");
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+/// A graded item, read from Canvas' `assignment_settings.xml`, a quiz's
+/// `assessment_meta.xml` or a graded discussion's `topicMeta`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Assignment {
+    pub identifier: String,
+    /// The manifest resource the assignment belongs to: the assignment
+    /// itself, or the quiz or discussion topic it grades.
+    pub resource_identifier: String,
+    pub title: String,
+    pub assignment_group_identifier_ref: Option<String>,
+    pub points_possible: Option<f64>,
+    pub grading_type: Option<String>,
+    pub grading_standard_identifier_ref: Option<String>,
+    pub rubric_identifier_ref: Option<String>,
+    /// The quiz, for assignments that grade one.
+    pub quiz_identifier_ref: Option<String>,
+    /// Whether the rubric's score becomes the assignment's grade.
+    pub rubric_use_for_grading: bool,
+    pub workflow_state: Option<String>,
+    pub position: Option<u32>,
+    pub submission_types: Vec<String>,
+    pub due_at: Option<String>,
+    pub unlock_at: Option<String>,
+    pub lock_at: Option<String>,
+    pub omit_from_final_grade: bool,
+    /// Every setting as exported, including the ones above.
+    pub settings: BTreeMap<String, String>,
+}
+
+impl Assignment {
+    pub fn new(identifier: Option<String>) -> Assignment {
+        Assignment {
+            identifier: identifier.unwrap_or_default(),
+            ..Assignment::default()
+        }
+    }
+
+    pub fn published(&self) -> bool {
+        self.workflow_state.as_deref() != Some("unpublished")
+    }
+
+    /// Whether the assignment counts toward the course grade at all.
+    pub fn counts_toward_grade(&self) -> bool {
+        self.published() && !self.omit_from_final_grade && self.grading_type.as_deref() != Some("not_graded")
+    }
+
+    /// Whether grades are shown through a grading scheme rather than as
+    /// points or percentages.
+    pub fn uses_grading_scheme(&self) -> bool {
+        matches!(self.grading_type.as_deref(), Some("letter_grade") | Some("gpa_scale"))
+    }
+}
+
+/// An assignment group from `course_settings/assignment_groups.xml`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AssignmentGroup {
+    pub identifier: String,
+    pub title: String,
+    pub position: Option<u32>,
+    /// Percentage of the final grade when the course weights groups.
+    pub group_weight: f64,
+    pub drop_lowest: u32,
+    pub drop_highest: u32,
+    /// Assignments the drop rules never remove.
+    pub never_drop: Vec<String>,
+}
+
+impl AssignmentGroup {
+    pub fn new(identifier: Option<String>) -> AssignmentGroup {
+        AssignmentGroup {
+            identifier: identifier.unwrap_or_default(),
+            ..AssignmentGroup::default()
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+/// Canvas course settings from `course_settings/course_settings.xml`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CourseSettings {
+    pub identifier: String,
+    pub title: String,
+    pub course_code: Option<String>,
+    pub start_at: Option<String>,
+    pub conclude_at: Option<String>,
+    pub default_view: Option<String>,
+    pub time_zone: Option<String>,
+    pub locale: Option<String>,
+    pub license: Option<String>,
+    pub grading_standard_enabled: bool,
+    pub grading_standard_identifier_ref: Option<String>,
+    pub group_weighting_scheme: Option<String>,
+    pub is_public: bool,
+    pub is_public_to_auth_users: bool,
+    pub public_syllabus: bool,
+    pub indexed: bool,
+    pub self_enrollment: bool,
+    pub open_enrollment: bool,
+    pub restrict_enrollments_to_course_dates: bool,
+    /// Course navigation, in order, from `tab_configuration`.
+    pub tabs: Vec<CourseTab>,
+    /// Every top-level setting as exported, including the ones above.
+    pub settings: BTreeMap<String, String>,
+}
+
+impl CourseSettings {
+    pub fn new(identifier: Option<String>) -> CourseSettings {
+        CourseSettings {
+            identifier: identifier.unwrap_or_default(),
+            ..CourseSettings::default()
+        }
+    }
+
+    pub fn hidden_tabs(&self) -> Vec<&str> {
+        self.tabs.iter().filter(|tab| tab.hidden).map(|tab| tab.id.as_str()).collect()
+    }
+
+    /// Canvas' `course_visibility`: `public`, `institution` or `course`.
+    pub fn visibility(&self) -> &str {
+        if self.is_public {
+            "public"
+        } else if self.is_public_to_auth_users {
+            "institution"
+        } else {
+            "course"
+        }
+    }
+}
+
+/// A course navigation tab. Built-in tabs have numeric ids (`0` is Home);
+/// external tools use ids like `context_external_tool_5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseTab {
+    pub id: String,
+    pub hidden: bool,
+}
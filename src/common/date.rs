@@ -0,0 +1,173 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar date and time without a zone, as Canvas writes them
+/// (`2016-04-01T06:00:00`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    pub fn from_unix_seconds(seconds: i64) -> DateTime {
+        let days = seconds.div_euclid(86_400);
+        let time = seconds.rem_euclid(86_400) as u32;
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time / 60 % 60,
+            second: time % 60,
+        }
+    }
+
+    /// Reads `2016-04-01T06:00:00` and `2016-04-01`. Fractional seconds and
+    /// a trailing `Z` are ignored; other offsets are not accepted, since
+    /// Canvas always exports UTC.
+    pub fn parse(value: &str) -> Option<DateTime> {
+        let value = value.trim().trim_end_matches('Z');
+        let (date, time) = match value.find('T') {
+            Some(index) => (&value[..index], &value[index + 1..]),
+            None => (value, "00:00:00"),
+        };
+        let time = time.split('.').next().unwrap_or("");
+        let date: Vec<&str> = date.split('-').collect();
+        let time: Vec<&str> = time.split(':').collect();
+        if date.len() != 3 || time.len() < 2 || time.len() > 3 {
+            return None;
+        }
+        let date_time = DateTime {
+            year: date[0].parse().ok()?,
+            month: date[1].parse().ok()?,
+            day: date[2].parse().ok()?,
+            hour: time[0].parse().ok()?,
+            minute: time[1].parse().ok()?,
+            second: time.get(2).map_or(Some(0), |second| second.parse().ok())?,
+        };
+        let valid = (1..=12).contains(&date_time.month)
+            && date_time.day >= 1
+            && date_time.day <= days_in_month(date_time.year, date_time.month)
+            && date_time.hour < 24
+            && date_time.minute < 60
+            && date_time.second < 60;
+        if valid {
+            Some(date_time)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_unix_seconds(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days * 86_400 + i64::from(self.hour * 3600 + self.minute * 60 + self.second)
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days_since_epoch(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+
+    /// The day of the week, with Sunday as 0 as Canvas numbers them.
+    pub fn weekday(&self) -> u32 {
+        (self.days_since_epoch() + 4).rem_euclid(7) as u32
+    }
+
+    /// The same time of day, `days` later.
+    pub fn add_days(&self, days: i64) -> DateTime {
+        let (year, month, day) = civil_from_days(self.days_since_epoch() + days);
+        DateTime { year, month, day, ..*self }
+    }
+
+    /// The iCalendar form, `20160401T060000Z`.
+    pub fn to_ics(&self) -> String {
+        format!("{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+                self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+
+    /// The current time in UTC.
+    pub fn now() -> DateTime {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64);
+        DateTime::from_unix_seconds(seconds)
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+               self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Howard Hinnant's `days_from_civil`, the inverse of `civil_from_days`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from(if month > 2 { month - 3 } else { month + 9 });
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Howard Hinnant's `civil_from_days`: days since 1970-01-01 to a date in
+/// the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateTime;
+
+    #[test]
+    fn converts_unix_seconds() {
+        assert_eq!(DateTime::from_unix_seconds(0).to_string(), "1970-01-01T00:00:00");
+        assert_eq!(DateTime::from_unix_seconds(1_459_490_400).to_string(), "2016-04-01T06:00:00");
+        assert_eq!(DateTime::from_unix_seconds(951_782_400).to_string(), "2000-02-29T00:00:00");
+        assert_eq!(DateTime::from_unix_seconds(-1).to_string(), "1969-12-31T23:59:59");
+    }
+
+    #[test]
+    fn parses_canvas_dates() {
+        let date = DateTime::parse("2016-04-01T06:00:00").unwrap();
+        assert_eq!(date.to_unix_seconds(), 1_459_490_400);
+        assert_eq!(date.to_ics(), "20160401T060000Z");
+        assert_eq!(DateTime::parse("2016-04-01T06:00:00.000Z"), Some(date));
+        assert_eq!(DateTime::parse("2016-04-01").map(|date| date.to_string()), Some("2016-04-01T00:00:00".to_string()));
+        assert_eq!(DateTime::parse("2015-02-29T00:00:00"), None);
+        assert_eq!(DateTime::parse("next tuesday"), None);
+    }
+
+    #[test]
+    fn does_calendar_arithmetic() {
+        let date = DateTime::parse("2016-02-28T06:00:00").unwrap();
+        assert_eq!(date.weekday(), 0);
+        assert_eq!(date.add_days(1).to_string(), "2016-02-29T06:00:00");
+        assert_eq!(date.add_days(-59).to_string(), "2015-12-31T06:00:00");
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::BTreeMap;
+
+/// A course calendar event from `course_settings/events.xml`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CalendarEvent {
+    pub identifier: String,
+    pub title: String,
+    /// HTML.
+    pub description: Option<String>,
+    pub start_at: Option<String>,
+    pub end_at: Option<String>,
+    pub location_name: Option<String>,
+    pub location_address: Option<String>,
+    pub all_day: bool,
+    /// Every field as exported, including the ones above.
+    pub settings: BTreeMap<String, String>,
+}
+
+impl CalendarEvent {
+    pub fn new(identifier: Option<String>) -> CalendarEvent {
+        CalendarEvent {
+            identifier: identifier.unwrap_or_default(),
+            ..CalendarEvent::default()
+        }
+    }
+
+    /// Events exported without a `workflow_state` count as published.
+    pub fn published(&self) -> bool {
+        !matches!(self.settings.get("workflow_state").map(|state| state.trim()), Some("unpublished") | Some("deleted"))
+    }
+}
@@ -0,0 +1,24 @@
+/// The system that exported the cartridge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Exporter {
+    Canvas,
+    Moodle,
+    Blackboard,
+    D2L,
+    Schoology,
+    #[default]
+    Unknown,
+}
+
+impl Exporter {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Exporter::Canvas => "Canvas",
+            Exporter::Moodle => "Moodle",
+            Exporter::Blackboard => "Blackboard Learn",
+            Exporter::D2L => "D2L Brightspace",
+            Exporter::Schoology => "Schoology",
+            Exporter::Unknown => "Unknown",
+        }
+    }
+}
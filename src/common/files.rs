@@ -0,0 +1,373 @@
+use common::normalize_href;
+use std::io::{ErrorKind, Read, Result};
+
+/// The course files under `web_resources/`, with the folder and file state
+/// Canvas records in `course_settings/files_meta.xml`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FileTree {
+    pub root: Folder,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Folder {
+    pub name: String,
+    /// Relative to `web_resources/`, without a trailing slash. Empty for
+    /// the root.
+    pub path: String,
+    pub hidden: bool,
+    pub locked: bool,
+    pub unlock_at: Option<String>,
+    pub lock_at: Option<String>,
+    pub folders: Vec<Folder>,
+    pub files: Vec<FileEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FileEntry {
+    pub name: String,
+    /// The path in the archive, including `web_resources/`, with any
+    /// percent-escapes decoded.
+    pub path: String,
+    /// The manifest resource for the file, if there is one.
+    pub identifier: Option<String>,
+    /// The name shown in Canvas when it differs from the file name.
+    pub display_name: Option<String>,
+    pub size: u64,
+    pub compressed_size: u64,
+    pub crc32: u32,
+    pub mime_type: String,
+    pub hidden: bool,
+    pub locked: bool,
+    pub unlock_at: Option<String>,
+    pub lock_at: Option<String>,
+    pub usage_rights: Option<UsageRights>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UsageRights {
+    /// `own_copyright`, `used_by_permission`, `fair_use`, `public_domain` or
+    /// `creative_commons`.
+    pub use_justification: Option<String>,
+    pub legal_copyright: Option<String>,
+    pub license: Option<String>,
+}
+
+pub const FILES_ROOT: &str = "web_resources/";
+
+impl FileTree {
+    pub fn new() -> FileTree {
+        FileTree::default()
+    }
+
+    /// Adds a file under `web_resources/`, creating its folders.
+    pub fn insert(&mut self, file: FileEntry) {
+        let relative = file.path.trim_start_matches(FILES_ROOT).to_string();
+        let mut folder = &mut self.root;
+        let mut segments: Vec<&str> = relative.split('/').collect();
+        segments.pop();
+        for segment in segments {
+            let path = if folder.path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}/{}", folder.path, segment)
+            };
+            let index = match folder.folders.iter().position(|child| child.name == segment) {
+                Some(index) => index,
+                None => {
+                    folder.folders.push(Folder { name: segment.to_string(), path, ..Folder::default() });
+                    folder.folders.len() - 1
+                }
+            };
+            folder = &mut folder.folders[index];
+        }
+        folder.files.push(file);
+    }
+
+    pub fn folder(&self, path: &str) -> Option<&Folder> {
+        let path = path.trim_matches('/');
+        if path.is_empty() {
+            return Some(&self.root);
+        }
+        let mut folder = &self.root;
+        for segment in path.split('/') {
+            folder = folder.folders.iter().find(|child| child.name == segment)?;
+        }
+        Some(folder)
+    }
+
+    pub fn folder_mut(&mut self, path: &str) -> Option<&mut Folder> {
+        let path = path.trim_matches('/').to_string();
+        let mut folder = &mut self.root;
+        if path.is_empty() {
+            return Some(folder);
+        }
+        for segment in path.split('/') {
+            folder = folder.folders.iter_mut().find(|child| child.name == segment)?;
+        }
+        Some(folder)
+    }
+
+    /// The file at an archive path, or the one an `href` like
+    /// `web_resources/Example%20File.jpg` refers to. As with `EntryNames`,
+    /// an `href` matches once normalized, or failing that ignoring case.
+    pub fn file(&self, href: &str) -> Option<&FileEntry> {
+        let path = self.find_path(href)?;
+        self.files().into_iter().find(|file| file.path == path)
+    }
+
+    pub fn file_mut(&mut self, href: &str) -> Option<&mut FileEntry> {
+        let path = self.find_path(href)?;
+        let relative = path.trim_start_matches(FILES_ROOT);
+        let folder = match relative.rfind('/') {
+            Some(slash) => self.folder_mut(&relative[..slash])?,
+            None => &mut self.root,
+        };
+        folder.files.iter_mut().find(|file| file.path == path)
+    }
+
+    fn find_path(&self, href: &str) -> Option<String> {
+        let normalized = normalize_href(href);
+        let files = self.files();
+        files
+            .iter()
+            .find(|file| file.path == href || file.path == normalized)
+            .or_else(|| files.iter().find(|file| file.path.to_lowercase() == normalized.to_lowercase()))
+            .map(|file| file.path.clone())
+    }
+
+    /// Every file, folder by folder.
+    pub fn files(&self) -> Vec<&FileEntry> {
+        let mut files = Vec::new();
+        self.root.collect_files(&mut files);
+        files
+    }
+
+    /// The tree as students see it, without hidden or locked files and
+    /// folders. Files only available between dates are kept, as Canvas
+    /// lists them.
+    pub fn student_view(&self) -> FileTree {
+        FileTree { root: self.root.student_view() }
+    }
+
+    /// Files students can't open right now: locked, hidden, or in a folder
+    /// that is, as well as those with availability dates.
+    pub fn restricted_files(&self) -> Vec<&FileEntry> {
+        let mut files = Vec::new();
+        self.root.collect_restricted(false, &mut files);
+        files
+    }
+}
+
+impl Folder {
+    /// The uncompressed size of every file in the folder and below.
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum::<u64>() + self.folders.iter().map(Folder::size).sum::<u64>()
+    }
+
+    pub fn compressed_size(&self) -> u64 {
+        self.files.iter().map(|file| file.compressed_size).sum::<u64>() +
+        self.folders.iter().map(Folder::compressed_size).sum::<u64>()
+    }
+
+    pub fn restricted(&self) -> bool {
+        self.hidden || self.locked || self.unlock_at.is_some() || self.lock_at.is_some()
+    }
+
+    fn student_view(&self) -> Folder {
+        Folder {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            hidden: self.hidden,
+            locked: self.locked,
+            unlock_at: self.unlock_at.clone(),
+            lock_at: self.lock_at.clone(),
+            folders: self.folders
+                .iter()
+                .filter(|folder| !folder.hidden && !folder.locked)
+                .map(Folder::student_view)
+                .collect(),
+            files: self.files.iter().filter(|file| !file.hidden && !file.locked).cloned().collect(),
+        }
+    }
+
+    fn collect_files<'a>(&'a self, files: &mut Vec<&'a FileEntry>) {
+        files.extend(self.files.iter());
+        for folder in &self.folders {
+            folder.collect_files(files);
+        }
+    }
+
+    fn collect_restricted<'a>(&'a self, inherited: bool, files: &mut Vec<&'a FileEntry>) {
+        let restricted = inherited || self.restricted();
+        files.extend(self.files.iter().filter(|file| restricted || file.restricted()));
+        for folder in &self.folders {
+            folder.collect_restricted(restricted, files);
+        }
+    }
+}
+
+impl FileEntry {
+    pub fn new(path: &str) -> FileEntry {
+        FileEntry {
+            name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            path: path.to_string(),
+            mime_type: mime_type(path).to_string(),
+            ..FileEntry::default()
+        }
+    }
+
+    pub fn restricted(&self) -> bool {
+        self.hidden || self.locked || self.unlock_at.is_some() || self.lock_at.is_some()
+    }
+}
+
+/// The MIME type for common course file extensions.
+pub fn mime_type(path: &str) -> &'static str {
+    let extension = match path.rfind('.') {
+        Some(dot) if !path[dot..].contains('/') => path[dot + 1..].to_lowercase(),
+        _ => return "application/octet-stream",
+    };
+    match extension.as_str() {
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "xml" => "application/xml",
+        "js" => "application/javascript",
+        "json" => "application/json",
+        "vtt" => "text/vtt",
+        "srt" => "application/x-subrip",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "zip" => "application/zip",
+        "mp4" | "m4v" => "video/mp4",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "ogv" => "video/ogg",
+        "avi" => "video/x-msvideo",
+        "wmv" => "video/x-ms-wmv",
+        "flv" => "video/x-flv",
+        "mpg" | "mpeg" => "video/mpeg",
+        "mkv" => "video/x-matroska",
+        "3gp" => "video/3gpp",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "wav" => "audio/wav",
+        "ogg" | "oga" => "audio/ogg",
+        "aac" => "audio/aac",
+        "flac" => "audio/flac",
+        "wma" => "audio/x-ms-wma",
+        _ => "application/octet-stream",
+    }
+}
+
+/// The CRC-32 (IEEE) checksum zip archives use.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finish()
+}
+
+/// The CRC-32 of everything `reader` yields, read a block at a time.
+pub fn crc32_of<R: Read>(mut reader: R) -> Result<u32> {
+    let mut crc = Crc32::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(crc.finish()),
+            Ok(read) => crc.update(&buffer[..read]),
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// A running CRC-32, fed a byte at a time from a lookup table.
+struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        Crc32 { crc: !0 }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.crc = CRC32_TABLE[((self.crc ^ u32::from(byte)) & 0xff) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.crc
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, crc32_of, mime_type, FileEntry, FileTree};
+
+    fn entry(path: &str, size: u64) -> FileEntry {
+        FileEntry { size, compressed_size: size / 2, ..FileEntry::new(path) }
+    }
+
+    #[test]
+    fn builds_folders_from_paths() {
+        let mut tree = FileTree::new();
+        tree.insert(entry("web_resources/Example File.jpg", 100));
+        tree.insert(entry("web_resources/Example Folder/Example.doc", 40));
+        tree.insert(entry("web_resources/Example Folder/Nested/notes.txt", 10));
+        assert_eq!(tree.root.files.len(), 1);
+        assert_eq!(tree.folder("Example Folder/Nested").unwrap().path, "Example Folder/Nested");
+        assert_eq!(tree.folder("Example Folder").unwrap().size(), 50);
+        assert_eq!(tree.root.size(), 150);
+        assert_eq!(tree.root.compressed_size(), 75);
+        assert_eq!(tree.file("web_resources/Example Folder/Example.doc").unwrap().mime_type, "application/msword");
+        assert_eq!(tree.files().len(), 3);
+
+        tree.folder_mut("Example Folder").unwrap().locked = true;
+        tree.file_mut("web_resources/Example File.jpg").unwrap().unlock_at = Some("2016-01-11".to_string());
+        assert_eq!(tree.file("web_resources/Example%20File.jpg").unwrap().name, "Example File.jpg");
+        assert_eq!(tree.file("Web_Resources/example folder/EXAMPLE.doc").unwrap().size, 40);
+        assert!(tree.file("web_resources/Example.doc").is_none());
+        assert_eq!(tree.restricted_files().len(), 3);
+    }
+
+    #[test]
+    fn identifies_files() {
+        assert_eq!(mime_type("web_resources/sample_mpeg4.MP4"), "video/mp4");
+        assert_eq!(mime_type("web_resources/v1.0/README"), "application/octet-stream");
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32_of(&b"123456789"[..]).unwrap(), 0xcbf4_3926);
+    }
+}
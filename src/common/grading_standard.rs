@@ -0,0 +1,50 @@
+/// A letter grade scheme from `course_settings/grading_standards.xml`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GradingStandard {
+    pub identifier: String,
+    pub title: String,
+    /// Cutoffs from the highest grade down.
+    pub cutoffs: Vec<GradeCutoff>,
+}
+
+/// The lowest score, as a fraction of 1, that earns `name`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeCutoff {
+    pub name: String,
+    pub min_score: f64,
+}
+
+impl GradingStandard {
+    pub fn new(identifier: Option<String>) -> GradingStandard {
+        GradingStandard {
+            identifier: identifier.unwrap_or_default(),
+            ..GradingStandard::default()
+        }
+    }
+
+    /// The scheme Canvas uses when letter grades are shown without one.
+    pub fn canvas_default() -> &'static GradingStandard {
+        lazy_static! {
+            static ref DEFAULT: GradingStandard = GradingStandard {
+                identifier: "".to_string(),
+                title: "Default Grading Scheme".to_string(),
+                cutoffs: [("A", 0.94), ("A-", 0.9), ("B+", 0.87), ("B", 0.84), ("B-", 0.8), ("C+", 0.77),
+                          ("C", 0.74), ("C-", 0.7), ("D+", 0.67), ("D", 0.64), ("D-", 0.61), ("F", 0.0)]
+                    .iter()
+                    .map(|&(name, min_score)| GradeCutoff { name: name.to_string(), min_score })
+                    .collect(),
+            };
+        }
+        &DEFAULT
+    }
+
+    /// The letter for a percentage between 0 and 100. Scores below every
+    /// cutoff get the lowest letter.
+    pub fn letter_for(&self, percentage: f64) -> Option<&str> {
+        self.cutoffs
+            .iter()
+            .find(|cutoff| percentage >= cutoff.min_score * 100.0 - 1e-9)
+            .or_else(|| self.cutoffs.last())
+            .map(|cutoff| cutoff.name.as_str())
+    }
+}
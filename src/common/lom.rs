@@ -0,0 +1,149 @@
+use common::LangString;
+
+/// IEEE LOM metadata, as found in the manifest-level `<lomimscc:lom>` and in
+/// resource-level `<metadata><lom:lom>` blocks.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Lom {
+    pub general: LomGeneral,
+    pub life_cycle: LomLifeCycle,
+    pub educational: LomEducational,
+    pub rights: LomRights,
+    pub classifications: Vec<LomClassification>,
+}
+
+impl Lom {
+    pub fn new() -> Lom {
+        Lom::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomGeneral {
+    pub identifiers: Vec<LomIdentifier>,
+    pub title: LangString,
+    pub languages: Vec<String>,
+    pub description: LangString,
+    pub keywords: Vec<LangString>,
+    pub coverage: Vec<LangString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomIdentifier {
+    pub catalog: String,
+    pub entry: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomLifeCycle {
+    pub version: LangString,
+    pub status: Option<String>,
+    pub contributors: Vec<LomContributor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomContributor {
+    pub role: Option<String>,
+    pub entities: Vec<VCard>,
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomEducational {
+    pub intended_end_user_roles: Vec<String>,
+    pub contexts: Vec<String>,
+    pub typical_learning_time: Option<LomDuration>,
+    pub description: LangString,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomDuration {
+    /// ISO 8601 duration, e.g. `PT1H30M`.
+    pub duration: String,
+    pub description: LangString,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomRights {
+    pub cost: Option<bool>,
+    pub copyright_and_other_restrictions: Option<bool>,
+    pub description: LangString,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomClassification {
+    pub purpose: Option<String>,
+    pub taxon_paths: Vec<LomTaxonPath>,
+    pub description: LangString,
+    pub keywords: Vec<LangString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomTaxonPath {
+    pub source: LangString,
+    pub taxa: Vec<LomTaxon>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LomTaxon {
+    pub id: String,
+    pub entry: LangString,
+}
+
+/// The handful of vCard properties LOM contributors actually carry. The
+/// original text is kept in `raw`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VCard {
+    pub raw: String,
+    pub formatted_name: Option<String>,
+    pub organization: Option<String>,
+    pub email: Option<String>,
+}
+
+impl VCard {
+    pub fn parse(text: &str) -> VCard {
+        let mut card = VCard { raw: text.trim().to_string(), ..VCard::default() };
+        for line in unfold_lines(text) {
+            let (key, value) = match line.find(':') {
+                Some(index) => (&line[..index], line[index + 1..].trim()),
+                None => continue,
+            };
+            if value.is_empty() {
+                continue;
+            }
+            // Property parameters (`EMAIL;TYPE=INTERNET:`) don't matter here.
+            let property = key.split(';').next().unwrap_or(key).trim().to_uppercase();
+            match property.as_str() {
+                "FN" => card.formatted_name = Some(value.to_string()),
+                "ORG" => card.organization = Some(value.replace(';', " ").trim().to_string()),
+                "EMAIL" => card.email = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        card
+    }
+}
+
+/// vCard continuation lines start with a space or tab.
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+        lines.push(line.trim_start().to_string());
+    }
+    lines
+}
+
+/// LOM yes/no vocabulary values.
+pub fn vocabulary_flag(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "yes" | "true" => Some(true),
+        "no" | "false" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,43 @@
+use export::captions::{self, Cue};
+
+/// A caption, subtitle or description track for a media file, from
+/// `course_settings/media_tracks.xml`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaTrack {
+    /// The resource of the video or audio file the track belongs to.
+    pub media_identifier_ref: String,
+    /// `subtitles`, `captions`, `descriptions`, `chapters` or `metadata`.
+    pub kind: String,
+    pub locale: Option<String>,
+    /// The track's own resource, when it is exported as a file.
+    pub identifier_ref: Option<String>,
+    /// The track as exported, in SRT or WebVTT.
+    pub content: String,
+}
+
+impl MediaTrack {
+    pub fn new(media_identifier_ref: Option<String>) -> MediaTrack {
+        MediaTrack {
+            media_identifier_ref: media_identifier_ref.unwrap_or_default(),
+            kind: "subtitles".to_string(),
+            ..MediaTrack::default()
+        }
+    }
+
+    /// Whether the track gives the spoken content as text.
+    pub fn is_caption(&self) -> bool {
+        self.kind == "subtitles" || self.kind == "captions"
+    }
+
+    pub fn cues(&self) -> Vec<Cue> {
+        captions::parse(&self.content)
+    }
+
+    pub fn to_vtt(&self) -> String {
+        captions::to_vtt(&self.cues())
+    }
+
+    pub fn to_srt(&self) -> String {
+        captions::to_srt(&self.cues())
+    }
+}
@@ -0,0 +1,790 @@
+pub mod assignment;
+pub mod course_settings;
+pub mod date;
+pub mod event;
+pub mod exporter;
+pub mod files;
+pub mod grading_standard;
+pub mod lom;
+pub mod media;
+pub mod organization;
+pub mod outcome;
+pub mod paths;
+pub mod rubric;
+pub mod time_zone;
+
+use analysis::{self, AlignmentReport, CaptionReport, GradeCalculator, PrerequisiteGraph, RestrictedFileLink, Timeline};
+use export;
+use std::collections::HashMap;
+use summarize::namespaces::Namespace;
+use summarize::utils;
+use xml::name::OwnedName;
+
+pub use self::assignment::{Assignment, AssignmentGroup};
+pub use self::course_settings::{CourseSettings, CourseTab};
+pub use self::date::DateTime;
+pub use self::event::CalendarEvent;
+pub use self::exporter::Exporter;
+pub use self::files::{crc32, crc32_of, mime_type, FileEntry, FileTree, Folder, UsageRights, FILES_ROOT};
+pub use self::grading_standard::{GradeCutoff, GradingStandard};
+pub use self::lom::{Lom, LomClassification, LomContributor, LomDuration, LomEducational, LomGeneral,
+                    LomIdentifier, LomLifeCycle, LomRights, LomTaxon, LomTaxonPath, VCard};
+pub use self::media::MediaTrack;
+pub use self::organization::{Organization, OrganizationItem};
+pub use self::paths::{join_base, normalize_entry_name, normalize_href, resolve_href, EntryNames};
+pub use self::outcome::{Outcome, OutcomeAlignment, OutcomeGroup, OutcomeRating, QuestionBank};
+pub use self::rubric::{Rubric, RubricCriterion, RubricRating};
+pub use self::time_zone::{DaylightSaving, TimeZone};
+
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub identifier: String,
+    pub general: General,
+    pub metadata: Lom,
+    /// Built from the default organization.
+    pub modules: Vec<Module>,
+    pub organizations: Vec<Organization>,
+    /// The `default` attribute of `<organizations>`.
+    pub default_organization: Option<String>,
+    pub resources: Vec<Resource>,
+    /// Nested `<manifest>`s, each with its own organizations and resources.
+    /// Their identifiers only need to be unique within them.
+    pub sub_manifests: Vec<Manifest>,
+}
+
+impl Manifest {
+    /// Finds a resource by identifier. Resources of sub-manifests are
+    /// scoped by the sub-manifest's identifier, as `sub_manifest/resource`.
+    pub fn find_resource(&self, identifier: &str) -> Option<&Resource> {
+        find_resource(&self.resources, &self.sub_manifests, identifier)
+    }
+}
+
+fn find_resource<'a>(resources: &'a [Resource], sub_manifests: &'a [Manifest], identifier: &str) -> Option<&'a Resource> {
+    match identifier.find('/') {
+        Some(slash) => sub_manifests
+            .iter()
+            .find(|manifest| manifest.identifier == identifier[..slash])
+            .and_then(|manifest| manifest.find_resource(&identifier[slash + 1..])),
+        None => resources.iter().find(|resource| resource.identifier == identifier),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ManifestBuilder {
+    pub identifier: String,
+    pub metadata: Lom,
+    pub modules: Vec<ModuleBuilder>,
+    pub organizations: Vec<Organization>,
+    pub default_organization: Option<String>,
+    pub resources_map: HashMap<String, Resource>,
+    pub sub_manifests: Vec<Manifest>,
+}
+
+impl ManifestBuilder {
+    pub fn new() -> ManifestBuilder {
+        ManifestBuilder {
+            identifier: String::new(),
+            metadata: Lom::new(),
+            modules: Vec::new(),
+            organizations: Vec::new(),
+            default_organization: None,
+            resources_map: HashMap::new(),
+            sub_manifests: Vec::new(),
+        }
+    }
+    pub fn finalize(self) -> Manifest {
+        let mut modules: Vec<Module> = Vec::new();
+        {
+            for builder in self.modules.into_iter() {
+                modules.push(builder.finalize(&self.resources_map));
+            }
+        }
+        let resources = self.resources_map
+            .into_iter()
+            .fold(Vec::new(), |mut acc, (_, val)| {
+                acc.push(val);
+                acc
+            });
+        Manifest {
+            identifier: self.identifier,
+            general: General::new(&self.metadata),
+            metadata: self.metadata,
+            modules,
+            organizations: self.organizations,
+            default_organization: self.default_organization,
+            resources,
+            sub_manifests: self.sub_manifests,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub identifier: String,
+    pub exporter: Exporter,
+    pub general: General,
+    pub metadata: Lom,
+    /// Built from the default organization.
+    pub modules: Vec<Module>,
+    pub organizations: Vec<Organization>,
+    pub resources: Vec<Resource>,
+    pub sub_manifests: Vec<Manifest>,
+    pub course_settings: Option<CourseSettings>,
+    pub assignments: Vec<Assignment>,
+    pub assignment_groups: Vec<AssignmentGroup>,
+    pub grading_standards: Vec<GradingStandard>,
+    pub rubrics: Vec<Rubric>,
+    /// The root of the learning outcome tree.
+    pub outcomes: OutcomeGroup,
+    pub question_banks: Vec<QuestionBank>,
+    pub events: Vec<CalendarEvent>,
+    pub media_tracks: Vec<MediaTrack>,
+    /// The course files under `web_resources/`.
+    pub files: FileTree,
+    /// The document of the resource marked `intendeduse="syllabus"`.
+    pub syllabus_html: Option<String>,
+}
+
+impl Summary {
+    pub fn new(manifest: Manifest) -> Summary {
+        Summary {
+            identifier: manifest.identifier,
+            exporter: Exporter::Unknown,
+            general: manifest.general,
+            metadata: manifest.metadata,
+            modules: manifest.modules,
+            organizations: manifest.organizations,
+            resources: manifest.resources,
+            sub_manifests: manifest.sub_manifests,
+            course_settings: None,
+            assignments: Vec::new(),
+            assignment_groups: Vec::new(),
+            grading_standards: Vec::new(),
+            rubrics: Vec::new(),
+            outcomes: OutcomeGroup::default(),
+            question_banks: Vec::new(),
+            events: Vec::new(),
+            media_tracks: Vec::new(),
+            files: FileTree::new(),
+            syllabus_html: None,
+        }
+    }
+
+    /// Finds a resource by identifier, scoped by sub-manifest as
+    /// `sub_manifest/resource`.
+    pub fn find_resource(&self, identifier: &str) -> Option<&Resource> {
+        find_resource(&self.resources, &self.sub_manifests, identifier)
+    }
+
+    pub fn prerequisite_graph(&self) -> PrerequisiteGraph<'_> {
+        PrerequisiteGraph::new(&self.modules)
+    }
+
+    /// A calculator using the course's assignment groups, drop rules and
+    /// weighting scheme.
+    pub fn grade_calculator(&self) -> GradeCalculator<'_> {
+        let weighted = self.course_settings
+            .as_ref()
+            .is_some_and(|settings| settings.group_weighting_scheme.as_deref() == Some("percent"));
+        GradeCalculator::new(&self.assignment_groups, &self.assignments, weighted)
+    }
+
+    pub fn grading_standard(&self, identifier: &str) -> Option<&GradingStandard> {
+        self.grading_standards.iter().find(|standard| standard.identifier == identifier)
+    }
+
+    /// The scheme for the course's final grade, if letter grades are enabled.
+    /// Canvas falls back to its default scheme when none is referenced.
+    pub fn course_grading_standard(&self) -> Option<&GradingStandard> {
+        let settings = self.course_settings.as_ref().filter(|settings| settings.grading_standard_enabled)?;
+        Some(settings
+            .grading_standard_identifier_ref
+            .as_deref()
+            .and_then(|identifier| self.grading_standard(identifier))
+            .unwrap_or_else(|| GradingStandard::canvas_default()))
+    }
+
+    /// The scheme for an assignment graded with letters or a GPA scale: its
+    /// own, else the course's, else Canvas' default.
+    pub fn assignment_grading_standard(&self, assignment: &Assignment) -> Option<&GradingStandard> {
+        if !assignment.uses_grading_scheme() {
+            return None;
+        }
+        Some(assignment
+            .grading_standard_identifier_ref
+            .as_deref()
+            .and_then(|identifier| self.grading_standard(identifier))
+            .or_else(|| self.course_grading_standard())
+            .unwrap_or_else(|| GradingStandard::canvas_default()))
+    }
+
+    pub fn rubric_for(&self, assignment: &Assignment) -> Option<&Rubric> {
+        let identifier = assignment.rubric_identifier_ref.as_deref()?;
+        self.rubrics.iter().find(|rubric| rubric.identifier == identifier)
+    }
+
+    /// Assignments the rubric is attached to.
+    pub fn rubric_assignments(&self, rubric: &Rubric) -> Vec<&Assignment> {
+        self.assignments
+            .iter()
+            .filter(|assignment| assignment.rubric_identifier_ref.as_deref() == Some(rubric.identifier.as_str()))
+            .collect()
+    }
+
+    pub fn outcome_alignment_report(&self) -> AlignmentReport {
+        AlignmentReport::new(&self.outcomes, &self.assignments, &self.rubrics, &self.question_banks)
+    }
+
+    /// The learning outcomes as an IMS CASE `CFPackage` JSON document, last
+    /// changed at `last_change`.
+    pub fn outcomes_to_case(&self, base_uri: &str, last_change: &DateTime) -> String {
+        export::case::package(self, base_uri, last_change)
+    }
+
+    pub fn timeline(&self) -> Timeline {
+        Timeline::new(self)
+    }
+
+    /// Tracks for the video or audio file with the given resource identifier.
+    pub fn media_tracks_for(&self, identifier: &str) -> Vec<&MediaTrack> {
+        self.media_tracks.iter().filter(|track| track.media_identifier_ref == identifier).collect()
+    }
+
+    pub fn caption_report(&self) -> CaptionReport {
+        CaptionReport::new(&self.resources, &self.media_tracks)
+    }
+
+    /// The resource marked as the course syllabus.
+    pub fn syllabus_resource(&self) -> Option<&Resource> {
+        self.resources.iter().find(|resource| resource.intended_use == IntendedUse::Syllabus)
+    }
+
+    /// The syllabus body, as HTML and as plain text. Canvas exports an empty
+    /// body when the course has no syllabus, which gives `None`.
+    pub fn syllabus(&self) -> Option<Syllabus> {
+        let html = body(self.syllabus_html.as_ref()?).trim().to_string();
+        if html.is_empty() {
+            return None;
+        }
+        Some(Syllabus {
+            text: export::strip_tags(&html),
+            html,
+        })
+    }
+
+    /// Module items that point at files students can't open.
+    pub fn restricted_file_links(&self) -> Vec<RestrictedFileLink<'_>> {
+        analysis::restricted_file_links(&self.modules, &self.resources, &self.files)
+    }
+
+    /// The course as a student would see it: unpublished modules, items,
+    /// assignments and events, hidden or locked files and folders, and
+    /// anything intended only for instructors or mentors are left out.
+    pub fn student_view(&self) -> Summary {
+        let modules = self.modules
+            .iter()
+            .filter(|module| module.published)
+            .map(|module| {
+                let items = module.items
+                    .iter()
+                    .filter(|item| item.published && item.audience == Audience::Everyone)
+                    .cloned()
+                    .collect();
+                Module { items, ..module.clone() }
+            })
+            .collect();
+        let resources = self.resources
+            .iter()
+            .filter(|resource| resource.audience == Audience::Everyone)
+            .cloned()
+            .collect();
+        Summary {
+            identifier: self.identifier.clone(),
+            exporter: self.exporter,
+            general: self.general.clone(),
+            metadata: self.metadata.clone(),
+            modules,
+            organizations: self.organizations.clone(),
+            resources,
+            sub_manifests: self.sub_manifests.clone(),
+            course_settings: self.course_settings.clone(),
+            assignments: self.assignments.iter().filter(|assignment| assignment.published()).cloned().collect(),
+            assignment_groups: self.assignment_groups.clone(),
+            grading_standards: self.grading_standards.clone(),
+            rubrics: self.rubrics.clone(),
+            outcomes: self.outcomes.clone(),
+            question_banks: self.question_banks.clone(),
+            events: self.events.iter().filter(|event| event.published()).cloned().collect(),
+            media_tracks: self.media_tracks.clone(),
+            files: self.files.student_view(),
+            syllabus_html: self.syllabus_html.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Syllabus {
+    pub html: String,
+    pub text: String,
+}
+
+/// The contents of an HTML document's `<body>`, or the whole text when it
+/// has none. Tags are found ASCII case-insensitively, which keeps byte
+/// offsets the same as in `html`.
+fn body(html: &str) -> &str {
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<body").and_then(|start| lower[start..].find('>').map(|end| start + end + 1));
+    match start {
+        Some(start) => {
+            let end = lower[start..].find("</body").map_or(html.len(), |end| start + end);
+            &html[start..end]
+        }
+        None => html,
+    }
+}
+
+/// The headline fields of the manifest-level LOM. The complete metadata is
+/// available as `Manifest::metadata`.
+#[derive(Debug, Clone)]
+pub struct General {
+    pub title: LangString,
+    pub description: LangString,
+    pub keywords: Vec<LangString>,
+    pub copyright: LangString,
+}
+
+impl General {
+    pub fn new(lom: &Lom) -> General {
+        General {
+            title: lom.general.title.clone(),
+            description: lom.general.description.clone(),
+            keywords: lom.general.keywords.clone(),
+            copyright: lom.rights.description.clone(),
+        }
+    }
+}
+
+/// A LOM `langstring`: the same text in one or more languages, keyed by the
+/// `language` attribute of each `<string>` element.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LangString {
+    entries: Vec<(Option<String>, String)>,
+}
+
+impl LangString {
+    pub fn new() -> LangString {
+        LangString { entries: Vec::new() }
+    }
+
+    /// Adds a value, replacing any existing value for the same language.
+    pub fn insert(&mut self, language: Option<String>, value: String) -> &mut LangString {
+        let language = language
+            .map(|lang| lang.trim().to_string())
+            .filter(|lang| !lang.is_empty());
+        let existing = self.entries.iter().position(|(lang, _)| same_language(lang, &language));
+        match existing {
+            Some(index) => self.entries[index].1 = value,
+            None => self.entries.push((language, value)),
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn languages(&self) -> Vec<&str> {
+        self.entries.iter().filter_map(|(lang, _)| lang.as_deref()).collect()
+    }
+
+    /// Value for a language tag. An exact (case-insensitive) match wins;
+    /// otherwise tags sharing the same primary subtag match, so `fr` finds
+    /// `fr-CA` and `en-US` finds `en`.
+    pub fn get(&self, language: &str) -> Option<&str> {
+        let exact = self.entries.iter().find(|(lang, _)| {
+            lang.as_ref().is_some_and(|l| l.eq_ignore_ascii_case(language))
+        });
+        if let Some((_, value)) = exact {
+            return Some(value.as_str());
+        }
+        let primary = primary_subtag(language);
+        self.entries
+            .iter()
+            .find(|(lang, _)| lang.as_ref().is_some_and(|l| primary_subtag(l).eq_ignore_ascii_case(primary)))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Value for the first of `languages` that is present, falling back to
+    /// `value()` when none of them are.
+    pub fn preferred(&self, languages: &[&str]) -> &str {
+        languages
+            .iter()
+            .find_map(|lang| self.get(lang))
+            .unwrap_or_else(|| self.value())
+    }
+
+    /// The untagged value if there is one, otherwise the first value seen.
+    /// Empty when the langstring has no values.
+    pub fn value(&self) -> &str {
+        self.entries
+            .iter()
+            .find(|(lang, _)| lang.is_none())
+            .or_else(|| self.entries.first())
+            .map_or("", |(_, value)| value.as_str())
+    }
+}
+
+fn same_language(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn primary_subtag(language: &str) -> &str {
+    language.split(['-', '_']).next().unwrap_or(language)
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleItem {
+    pub identifier: String,
+    pub identifier_ref: String,
+    pub title: String,
+    pub item_type: ItemType,
+    pub audience: Audience,
+    pub published: bool,
+    /// Canvas `content_type`, e.g. `Assignment` or `ContextExternalTool`.
+    pub content_type: Option<String>,
+    pub position: Option<u32>,
+    pub indent: u32,
+    pub new_tab: bool,
+    pub url: Option<String>,
+}
+
+impl ModuleItem {
+    pub fn new(identifier: String, identifier_ref: String, title: String, i_type: ItemType) -> ModuleItem {
+        ModuleItem {
+            identifier,
+            identifier_ref,
+            title,
+            item_type: i_type,
+            audience: Audience::Everyone,
+            published: true,
+            content_type: None,
+            position: None,
+            indent: 0,
+            new_tab: false,
+            url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub identifier: String,
+    pub title: String,
+    pub items: Vec<ModuleItem>,
+    pub published: bool,
+    pub workflow_state: Option<String>,
+    pub position: Option<u32>,
+    pub locked: bool,
+    pub unlock_at: Option<String>,
+    pub require_sequential_progress: bool,
+    pub prerequisites: Vec<ModulePrerequisite>,
+    pub completion_requirements: Vec<CompletionRequirement>,
+}
+
+impl Module {
+    pub fn new(identifier: String, title: String, items: Vec<ModuleItem>) -> Module {
+        Module {
+            identifier,
+            title,
+            items,
+            published: true,
+            workflow_state: None,
+            position: None,
+            locked: false,
+            unlock_at: None,
+            require_sequential_progress: false,
+            prerequisites: Vec::new(),
+            completion_requirements: Vec::new(),
+        }
+    }
+}
+
+/// Another module that must be completed before this one unlocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModulePrerequisite {
+    /// Canvas only exports `context_module` prerequisites.
+    pub prerequisite_type: String,
+    pub title: String,
+    pub identifier_ref: String,
+}
+
+/// What a student has to do with a module item for the module to count as
+/// complete. `identifier_ref` points at a module item, not a resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionRequirement {
+    pub requirement_type: CompletionRequirementType,
+    pub identifier_ref: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompletionRequirementType {
+    MustView,
+    MustSubmit,
+    MustContribute,
+    MustMarkDone,
+    MinScore { min_score: f64 },
+    Other { type_string: String },
+}
+
+impl CompletionRequirementType {
+    pub fn new(type_string: &str) -> CompletionRequirementType {
+        match type_string {
+            "must_view" => CompletionRequirementType::MustView,
+            "must_submit" => CompletionRequirementType::MustSubmit,
+            "must_contribute" => CompletionRequirementType::MustContribute,
+            "must_mark_done" => CompletionRequirementType::MustMarkDone,
+            "min_score" => CompletionRequirementType::MinScore { min_score: 0.0 },
+            _ => CompletionRequirementType::Other { type_string: type_string.to_string() },
+        }
+    }
+}
+
+/// Who a resource is meant for, from the `educational/intendedEndUserRole`
+/// of its LOM metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Audience {
+    #[default]
+    Everyone,
+    Instructor,
+    Mentor,
+}
+
+impl Audience {
+    /// Resources without roles, or that list `Learner`, are for everyone.
+    pub fn from_lom(lom: &Lom) -> Audience {
+        let roles = &lom.educational.intended_end_user_roles;
+        let has_role = |name: &str| roles.iter().any(|role| role.trim().eq_ignore_ascii_case(name));
+        if roles.is_empty() || has_role("Learner") {
+            Audience::Everyone
+        } else if has_role("Instructor") {
+            Audience::Instructor
+        } else if has_role("Mentor") {
+            Audience::Mentor
+        } else {
+            Audience::Everyone
+        }
+    }
+}
+
+/// A resource's `intendeduse` attribute. Canvas marks the syllabus this way.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IntendedUse {
+    Syllabus,
+    LessonPlan,
+    Assignment,
+    #[default]
+    Unspecified,
+}
+
+impl IntendedUse {
+    pub fn new(value: &str) -> IntendedUse {
+        match value.trim().to_lowercase().as_str() {
+            "syllabus" => IntendedUse::Syllabus,
+            "lessonplan" => IntendedUse::LessonPlan,
+            "assignment" => IntendedUse::Assignment,
+            _ => IntendedUse::Unspecified,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ItemType {
+    Assignment,
+    Assessment,
+    DiscussionTopic,
+    WebContent,
+    WebLink,
+    /// A text-only heading in a module, with no content behind it.
+    SubHeader,
+    ExternalUrl,
+    ExternalTool,
+    #[default]
+    NoType,
+    Unknown { type_string: String },
+}
+
+#[derive(Debug, Default)]
+pub struct ModuleBuilder {
+    pub identifier: String,
+    pub title: String,
+    pub items: Vec<ModuleItemBuilder>,
+}
+
+impl ModuleBuilder {
+    pub fn new(identifier: Option<String>) -> ModuleBuilder {
+        ModuleBuilder {
+            identifier: identifier.unwrap_or_default(),
+            title: "".to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn title(&mut self, title: String) -> &mut ModuleBuilder {
+        self.title = title;
+        self
+    }
+
+    pub fn finalize(self, resources: &HashMap<String, Resource>) -> Module {
+        let items = self.items
+            .into_iter()
+            .map(|s_item| s_item.finalize(resources))
+            .collect::<Vec<ModuleItem>>();
+        Module::new(self.identifier, self.title, items)
+    }
+}
+
+#[derive(Debug)]
+pub struct ModuleItemBuilder {
+    pub identifier: String,
+    pub title: String,
+    pub identifier_ref: String,
+    /// How many items deep below the module the item is nested.
+    pub indent: u32,
+}
+
+impl ModuleItemBuilder {
+    pub fn new(identifier: Option<String>, i_ref: Option<String>) -> ModuleItemBuilder {
+        ModuleItemBuilder {
+            identifier: identifier.unwrap_or_default(),
+            title: "".to_string(),
+            identifier_ref: i_ref.unwrap_or("".to_string()),
+            indent: 0,
+        }
+    }
+
+    pub fn title(&mut self, title: String) -> &mut ModuleItemBuilder {
+        self.title = title;
+        self
+    }
+
+    pub fn finalize(self, resources: &HashMap<String, Resource>) -> ModuleItem {
+        let resource = resources.get(self.identifier_ref.as_str());
+        // An item that points at nothing is only a heading in the outline.
+        let i_type = if self.identifier_ref.is_empty() {
+            ItemType::SubHeader
+        } else {
+            resource.map_or(ItemType::NoType, |resource| resource.item_type.clone())
+        };
+        let mut item = ModuleItem::new(self.identifier, self.identifier_ref, self.title, i_type);
+        item.indent = self.indent;
+        if let Some(resource) = resource {
+            item.audience = resource.audience;
+        }
+        item
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Resource {
+    /// Resolved against any `xml:base` and normalized to an archive path.
+    /// Look it up with `EntryNames`, as the entry may be stored encoded.
+    pub href: Option<String>,
+    pub identifier: String,
+    pub item_type: ItemType,
+    pub metadata: Option<Lom>,
+    pub audience: Audience,
+    pub intended_use: IntendedUse,
+    /// The `href` of each `<file>`, resolved like `href`.
+    pub files: Vec<String>,
+    /// The data file Blackboard's `bb:file` names for the resource.
+    pub extension_file: Option<String>,
+}
+
+impl Resource {
+    pub fn new (node: &utils::Node) -> Resource {
+        let item_type = utils::typestr_to_type(node.find("type").unwrap_or("".to_string()).as_str());
+        let identifier = match node.find("identifier") {
+            Some(ident) => ident,
+            None => panic!("Malformed Manifest! A resource does not have an identifier.")
+        };
+        Resource {
+            href: node.find("href"),
+            identifier,
+            item_type,
+            metadata: None,
+            audience: Audience::Everyone,
+            intended_use: node.find("intendeduse").map_or(IntendedUse::Unspecified, |value| IntendedUse::new(&value)),
+            files: Vec::new(),
+            extension_file: node.attributes
+                .iter()
+                .find(|attr| {
+                    attr.name.local_name == "file" &&
+                    attr.name.namespace.as_ref().is_some_and(|uri| Namespace::Blackboard.contains(uri))
+                })
+                .map(|attr| attr.value.clone()),
+        }
+    }
+
+    /// The resource's main file: its `href`, or else its first `<file>`.
+    pub fn main_file(&self) -> Option<&str> {
+        self.href.as_deref().or_else(|| self.files.first().map(String::as_str))
+    }
+
+    pub fn set_metadata(&mut self, metadata: Lom) {
+        self.audience = Audience::from_lom(&metadata);
+        self.metadata = Some(metadata);
+    }
+}
+
+pub trait ParseHandler {
+    fn enter(&mut self, node: utils::Node);
+    fn leave(&mut self,  name: OwnedName);
+    fn receive_chars(&mut self, chars: String);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CalendarEvent, FileEntry, ManifestBuilder, Summary};
+
+    #[test]
+    fn extracts_syllabus_body() {
+        let mut summary = Summary::new(ManifestBuilder::new().finalize());
+        summary.syllabus_html = Some("<html>\n<head>\n<title>Syllabus</title>\n</head>\n<BODY class=\"x\">\n</BODY>\n</html>".to_string());
+        assert_eq!(summary.syllabus(), None);
+        summary.syllabus_html = Some("<html><head><title>Syllabus</title></head>\n<body>\n<h2>Grading</h2>\
+                                      <p>Essays &amp; exams</p>\n</body></html>".to_string());
+        let syllabus = summary.syllabus().unwrap();
+        assert_eq!(syllabus.html, "<h2>Grading</h2><p>Essays &amp; exams</p>");
+        assert_eq!(syllabus.text, "Grading Essays & exams");
+        summary.syllabus_html = Some("<title>İ</title><body>éé</body>".to_string());
+        assert_eq!(summary.syllabus().unwrap().html, "éé");
+        summary.syllabus_html = Some("<head><title>İİİİ Syllabus</title></head><BODY><p>Hi</p></BODY>".to_string());
+        assert_eq!(summary.syllabus().unwrap().html, "<p>Hi</p>");
+    }
+
+    #[test]
+    fn hides_restricted_files_and_unpublished_events_from_students() {
+        let mut summary = Summary::new(ManifestBuilder::new().finalize());
+        summary.files.insert(FileEntry::new("web_resources/syllabus.pdf"));
+        summary.files.insert(FileEntry { hidden: true, ..FileEntry::new("web_resources/answers.pdf") });
+        summary.files.insert(FileEntry { locked: true, ..FileEntry::new("web_resources/exam.pdf") });
+        summary.files.insert(FileEntry::new("web_resources/Drafts/notes.pdf"));
+        summary.files.folder_mut("Drafts").unwrap().hidden = true;
+        let mut draft = CalendarEvent::new(Some("e2".to_string()));
+        draft.settings.insert("workflow_state".to_string(), "unpublished".to_string());
+        summary.events = vec![CalendarEvent::new(Some("e1".to_string())), draft];
+
+        let student_view = summary.student_view();
+        let files: Vec<&str> = student_view.files.files().iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(files, vec!["web_resources/syllabus.pdf"]);
+        assert!(student_view.files.folder("Drafts").is_none());
+        assert_eq!(student_view.events.len(), 1);
+        assert_eq!(student_view.events[0].identifier, "e1");
+    }
+}
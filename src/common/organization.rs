@@ -0,0 +1,70 @@
+/// An `<organization>`: one arrangement of the cartridge's content. Canvas
+/// writes a single one; other packages may offer several, such as
+/// alternate sequences through the same resources.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Organization {
+    pub identifier: String,
+    /// `rooted-hierarchy` in Common Cartridge.
+    pub structure: Option<String>,
+    pub title: Option<String>,
+    pub items: Vec<OrganizationItem>,
+}
+
+impl Organization {
+    pub fn new(identifier: Option<String>) -> Organization {
+        Organization {
+            identifier: identifier.unwrap_or_default(),
+            ..Organization::default()
+        }
+    }
+
+    /// Every item in document order, with its depth below the organization.
+    pub fn all_items(&self) -> Vec<(usize, &OrganizationItem)> {
+        let mut items = Vec::new();
+        for item in &self.items {
+            item.collect(0, &mut items);
+        }
+        items
+    }
+
+    /// Whether the modules sit under a single root item, like Canvas'
+    /// `LearningModules`, rather than directly in the organization as in
+    /// Moodle's sections. The root presents nothing itself, and its
+    /// children, the modules, all hold items or, when empty, present
+    /// nothing either.
+    pub fn wraps_modules(&self) -> bool {
+        match self.items.as_slice() {
+            [root] => {
+                root.identifier_ref.is_none() && !root.items.is_empty() &&
+                root.items.iter().all(|module| !module.items.is_empty() || module.identifier_ref.is_none())
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OrganizationItem {
+    pub identifier: String,
+    /// A resource, or a sub-manifest, the item presents.
+    pub identifier_ref: Option<String>,
+    pub title: String,
+    pub items: Vec<OrganizationItem>,
+}
+
+impl OrganizationItem {
+    pub fn new(identifier: Option<String>, identifier_ref: Option<String>) -> OrganizationItem {
+        OrganizationItem {
+            identifier: identifier.unwrap_or_default(),
+            identifier_ref,
+            ..OrganizationItem::default()
+        }
+    }
+
+    fn collect<'a>(&'a self, depth: usize, items: &mut Vec<(usize, &'a OrganizationItem)>) {
+        items.push((depth, self));
+        for item in &self.items {
+            item.collect(depth + 1, items);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+/// A learning outcome group from `course_settings/learning_outcomes.xml`.
+/// The file's top level is a group with an empty identifier.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OutcomeGroup {
+    pub identifier: String,
+    pub title: String,
+    /// HTML.
+    pub description: Option<String>,
+    pub vendor_guid: Option<String>,
+    pub groups: Vec<OutcomeGroup>,
+    pub outcomes: Vec<Outcome>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Outcome {
+    pub identifier: String,
+    pub title: String,
+    /// HTML.
+    pub description: Option<String>,
+    pub vendor_guid: Option<String>,
+    pub calculation_method: Option<String>,
+    pub calculation_int: Option<u32>,
+    pub points_possible: Option<f64>,
+    pub mastery_points: Option<f64>,
+    /// The rating scale, from the highest score down.
+    pub ratings: Vec<OutcomeRating>,
+    pub alignments: Vec<OutcomeAlignment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OutcomeRating {
+    pub description: String,
+    pub points: f64,
+}
+
+/// Content that assesses an outcome. `content_type` is a Canvas class name
+/// such as `Rubric`, `Assignment` or `AssessmentQuestionBank`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OutcomeAlignment {
+    pub content_type: String,
+    pub content_id: String,
+    pub mastery_type: Option<String>,
+    pub mastery_score: Option<f64>,
+    pub position: Option<u32>,
+}
+
+impl OutcomeGroup {
+    pub fn new(identifier: Option<String>) -> OutcomeGroup {
+        OutcomeGroup {
+            identifier: identifier.unwrap_or_default(),
+            ..OutcomeGroup::default()
+        }
+    }
+
+    /// Every outcome in the group and its subgroups, depth first.
+    pub fn all_outcomes(&self) -> Vec<&Outcome> {
+        let mut outcomes: Vec<&Outcome> = self.outcomes.iter().collect();
+        for group in &self.groups {
+            outcomes.extend(group.all_outcomes());
+        }
+        outcomes
+    }
+
+    pub fn find_outcome(&self, identifier: &str) -> Option<&Outcome> {
+        self.all_outcomes().into_iter().find(|outcome| outcome.identifier == identifier)
+    }
+}
+
+impl Outcome {
+    pub fn new(identifier: Option<String>) -> Outcome {
+        Outcome {
+            identifier: identifier.unwrap_or_default(),
+            ..Outcome::default()
+        }
+    }
+}
+
+/// A question bank from `non_cc_assessments`. Quizzes that draw questions
+/// from a bank outside the course refer to one with no title.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QuestionBank {
+    pub identifier: String,
+    pub title: String,
+    /// Quizzes that pick questions from the bank.
+    pub quiz_identifiers: Vec<String>,
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+/// Turns an `href` into an archive path: any query or fragment is dropped,
+/// then the rest is normalized like an entry name. URLs with a scheme are
+/// returned unchanged.
+pub fn normalize_href(href: &str) -> String {
+    if has_scheme(href) {
+        return href.to_string();
+    }
+    normalize_entry_name(href.split(['?', '#']).next().unwrap_or(""))
+}
+
+/// Turns an archive entry name into the path an `href` normalizes to:
+/// backslashes become slashes, percent-escapes are decoded, and `.` and
+/// `..` segments are resolved. Unlike in an `href`, `?` and `#` are just
+/// part of the name.
+pub fn normalize_entry_name(name: &str) -> String {
+    let path = percent_decode(&name.replace('\\', "/"));
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    let mut normalized = segments.join("/");
+    if path.ends_with('/') && !normalized.is_empty() {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// Resolves `href` against an `xml:base`, then normalizes it. Cartridges
+/// write bases like `res1` and `res1/` alike, so a base is always taken as a
+/// folder.
+pub fn resolve_href(base: &str, href: &str) -> String {
+    normalize_href(&join_base(base, href))
+}
+
+/// Nests an `xml:base` inside an enclosing one.
+pub fn join_base(base: &str, child: &str) -> String {
+    if base.is_empty() || has_scheme(child) || child.starts_with('/') {
+        return child.to_string();
+    }
+    if base.ends_with('/') || base.ends_with('\\') {
+        format!("{}{}", base, child)
+    } else {
+        format!("{}/{}", base, child)
+    }
+}
+
+/// A URL like `http://…`, as opposed to a path. Single letters are taken
+/// for Windows drives.
+fn has_scheme(href: &str) -> bool {
+    match href.find(':') {
+        Some(colon) if colon > 1 => href[..colon]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ['+', '-', '.'].contains(&c)),
+        _ => false,
+    }
+}
+
+/// Decodes `%XX` escapes, leaving malformed ones, and text that wouldn't
+/// decode to UTF-8, as they are.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escape = match text.get(index + 1..index + 3) {
+            Some(hex) if bytes[index] == b'%' && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+                u8::from_str_radix(hex, 16).ok()
+            }
+            _ => None,
+        };
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| text.to_string())
+}
+
+/// The entry names of an archive, looked up by `href`. Exporters don't agree
+/// on whether names are stored percent-encoded, or on case, so an `href`
+/// matches an entry that normalizes to the same path, or failing that to
+/// the same path ignoring case.
+#[derive(Debug, Clone, Default)]
+pub struct EntryNames {
+    normalized: HashMap<String, String>,
+    folded: HashMap<String, String>,
+}
+
+impl EntryNames {
+    pub fn new<I: IntoIterator<Item = S>, S: AsRef<str>>(names: I) -> EntryNames {
+        EntryNames::within("", names)
+    }
+
+    /// Only the entries under `root`, looked up relative to it.
+    pub fn within<I: IntoIterator<Item = S>, S: AsRef<str>>(root: &str, names: I) -> EntryNames {
+        let mut entries = EntryNames::default();
+        for name in names {
+            let name = name.as_ref();
+            let normalized = match name.strip_prefix(root) {
+                Some(relative) => normalize_entry_name(relative),
+                None => continue,
+            };
+            entries.folded.entry(normalized.to_lowercase()).or_insert_with(|| name.to_string());
+            entries.normalized.entry(normalized).or_insert_with(|| name.to_string());
+        }
+        entries
+    }
+
+    /// The name of the entry `href` refers to.
+    pub fn find(&self, href: &str) -> Option<&str> {
+        let normalized = normalize_href(href);
+        self.normalized
+            .get(&normalized)
+            .or_else(|| self.folded.get(&normalized.to_lowercase()))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_entry_name, normalize_href, resolve_href, EntryNames};
+
+    #[test]
+    fn normalizes_hrefs() {
+        assert_eq!(normalize_href("web_resources/Example%20File.jpg"), "web_resources/Example File.jpg");
+        assert_eq!(normalize_href("web_resources\\Example Folder\\Example.doc"), "web_resources/Example Folder/Example.doc");
+        assert_eq!(normalize_href("./pages/../pages/intro.html#top"), "pages/intro.html");
+        assert_eq!(normalize_href("/100%.txt"), "100%.txt");
+        assert_eq!(normalize_href("caf%C3%A9.html"), "café.html");
+        assert_eq!(normalize_href("https://example.com/a%20b"), "https://example.com/a%20b");
+        assert_eq!(normalize_entry_name("web_resources\\Week #1/notes.pdf?v=2"), "web_resources/Week #1/notes.pdf?v=2");
+    }
+
+    #[test]
+    fn resolves_against_xml_base() {
+        assert_eq!(resolve_href("res1/", "page.html"), "res1/page.html");
+        assert_eq!(resolve_href("res1", "../shared/style.css"), "shared/style.css");
+        assert_eq!(resolve_href("", "page.html"), "page.html");
+        assert_eq!(resolve_href("res1/", "http://example.com/"), "http://example.com/");
+    }
+
+    #[test]
+    fn finds_entries_despite_encoding_and_case() {
+        let entries = EntryNames::new(vec!["imsmanifest.xml", "web_resources/Example%20File.jpg", "Pages/Intro.HTML"]);
+        assert_eq!(entries.find("web_resources/Example File.jpg"), Some("web_resources/Example%20File.jpg"));
+        assert_eq!(entries.find("web_resources/Example%20File.jpg"), Some("web_resources/Example%20File.jpg"));
+        assert_eq!(entries.find("pages/intro.html"), Some("Pages/Intro.HTML"));
+        assert_eq!(entries.find("missing.html"), None);
+        let entries = EntryNames::new(vec!["web_resources/Week #1/notes.pdf"]);
+        assert_eq!(entries.find("web_resources/Week%20%231/notes.pdf"), Some("web_resources/Week #1/notes.pdf"));
+        assert_eq!(entries.find("web_resources/Week #1/notes.pdf"), None);
+        let entries = EntryNames::within("My Course/", vec!["My Course/imsmanifest.xml", "imsmanifest.xml"]);
+        assert_eq!(entries.find("imsmanifest.xml"), Some("My Course/imsmanifest.xml"));
+    }
+}
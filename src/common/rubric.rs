@@ -0,0 +1,153 @@
+/// A rubric from `course_settings/rubrics.xml`. Assignments point to it
+/// through `Assignment::rubric_identifier_ref`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Rubric {
+    pub identifier: String,
+    pub title: String,
+    pub points_possible: Option<f64>,
+    pub free_form_criterion_comments: bool,
+    pub hide_score_total: bool,
+    pub criteria: Vec<RubricCriterion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RubricCriterion {
+    pub id: String,
+    pub description: String,
+    /// HTML, for criteria that come from outcomes.
+    pub long_description: Option<String>,
+    pub points: f64,
+    pub mastery_points: Option<f64>,
+    pub ignore_for_scoring: bool,
+    pub learning_outcome_identifier_ref: Option<String>,
+    /// From the highest score down.
+    pub ratings: Vec<RubricRating>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RubricRating {
+    pub id: String,
+    pub description: String,
+    pub long_description: Option<String>,
+    pub points: f64,
+}
+
+impl Rubric {
+    pub fn new(identifier: Option<String>) -> Rubric {
+        Rubric {
+            identifier: identifier.unwrap_or_default(),
+            ..Rubric::default()
+        }
+    }
+
+    /// Points from criteria that count toward the score.
+    pub fn scored_points(&self) -> f64 {
+        self.criteria
+            .iter()
+            .filter(|criterion| !criterion.ignore_for_scoring)
+            .map(|criterion| criterion.points)
+            .sum()
+    }
+
+    /// One row per rating; criteria without ratings get a single row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("criterion_id,criterion,criterion_points,rating_id,rating,rating_points,learning_outcome\r\n");
+        for criterion in &self.criteria {
+            let outcome = criterion.learning_outcome_identifier_ref.as_deref().unwrap_or("");
+            let mut row = |rating: Option<&RubricRating>| {
+                let fields = [
+                    criterion.id.clone(),
+                    criterion.description.clone(),
+                    format_points(criterion.points),
+                    rating.map_or(String::new(), |rating| rating.id.clone()),
+                    rating.map_or(String::new(), |rating| rating.description.clone()),
+                    rating.map_or(String::new(), |rating| format_points(rating.points)),
+                    outcome.to_string(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                csv.push_str(&fields.join(","));
+                csv.push_str("\r\n");
+            };
+            if criterion.ratings.is_empty() {
+                row(None);
+            }
+            for rating in &criterion.ratings {
+                row(Some(rating));
+            }
+        }
+        csv
+    }
+
+    /// A standalone page laid out like Canvas' rubric table, for printing.
+    /// All text comes from the cartridge and is escaped, long descriptions
+    /// included.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", html_escape(&self.title)));
+        html.push_str("<style>\n\
+                       table { border-collapse: collapse; width: 100%; }\n\
+                       th, td { border: 1px solid #666; padding: 0.4em; vertical-align: top; text-align: left; }\n\
+                       .points { white-space: nowrap; }\n\
+                       </style>\n</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", html_escape(&self.title)));
+        html.push_str("<table>\n<tr><th>Criteria</th><th>Ratings</th><th class=\"points\">Pts</th></tr>\n");
+        for criterion in &self.criteria {
+            html.push_str("<tr>\n<td>");
+            html.push_str(&html_escape(&criterion.description));
+            if let Some(ref long_description) = criterion.long_description {
+                html.push_str(&format!("<div class=\"long-description\">{}</div>", html_escape(long_description)));
+            }
+            if let Some(mastery_points) = criterion.mastery_points {
+                html.push_str(&format!("<div>Threshold: {} pts</div>", format_points(mastery_points)));
+            }
+            html.push_str("</td>\n<td><table><tr>");
+            for rating in &criterion.ratings {
+                html.push_str(&format!("<td><div class=\"points\">{} pts</div><div>{}</div>",
+                                       format_points(rating.points), html_escape(&rating.description)));
+                if let Some(ref long_description) = rating.long_description {
+                    html.push_str(&format!("<div>{}</div>", html_escape(long_description)));
+                }
+                html.push_str("</td>");
+            }
+            html.push_str("</tr></table></td>\n");
+            let points = if criterion.ignore_for_scoring {
+                "—".to_string()
+            } else {
+                format!("{} pts", format_points(criterion.points))
+            };
+            html.push_str(&format!("<td class=\"points\">{}</td>\n</tr>\n", points));
+        }
+        if !self.hide_score_total {
+            html.push_str(&format!("<tr><th colspan=\"3\">Total Points: {}</th></tr>\n",
+                                   format_points(self.points_possible.unwrap_or_else(|| self.scored_points()))));
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+}
+
+/// Whole numbers without a trailing `.0`.
+fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{}", points as i64)
+    } else {
+        points.to_string()
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
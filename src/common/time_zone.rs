@@ -0,0 +1,160 @@
+use common::DateTime;
+
+/// A course's time zone, for the calculations that need local days, like
+/// moving dates to a weekday. Zones are known by their IANA names, as in
+/// Canvas' `time_zone` course setting, with today's daylight saving rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeZone {
+    /// Seconds east of UTC outside daylight saving time.
+    pub standard_offset: i64,
+    pub daylight_saving: DaylightSaving,
+}
+
+/// When a zone is an hour ahead of its standard offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DaylightSaving {
+    #[default]
+    None,
+    /// From 2:00 local time on the second Sunday in March to 2:00 on the
+    /// first Sunday in November.
+    NorthAmerica,
+    /// From 1:00 UTC on the last Sunday in March to 1:00 UTC on the last
+    /// Sunday in October.
+    Europe,
+}
+
+impl TimeZone {
+    pub fn utc() -> TimeZone {
+        TimeZone::default()
+    }
+
+    pub fn fixed(standard_offset: i64) -> TimeZone {
+        TimeZone { standard_offset, daylight_saving: DaylightSaving::None }
+    }
+
+    /// The zone for an IANA name like `America/Denver`, if it's one of the
+    /// zones courses are commonly set to.
+    pub fn named(name: &str) -> Option<TimeZone> {
+        use self::DaylightSaving::{Europe, NorthAmerica};
+        let (hours, minutes, daylight_saving) = match name.trim() {
+            "UTC" | "Etc/UTC" | "GMT" | "Etc/GMT" => (0, 0, DaylightSaving::None),
+            "America/St_Johns" => (-3, -30, NorthAmerica),
+            "America/Halifax" => (-4, 0, NorthAmerica),
+            "America/New_York" | "America/Detroit" | "America/Toronto" | "America/Indiana/Indianapolis" |
+            "America/Kentucky/Louisville" | "US/Eastern" => (-5, 0, NorthAmerica),
+            "America/Chicago" | "America/Winnipeg" | "US/Central" => (-6, 0, NorthAmerica),
+            "America/Regina" => (-6, 0, DaylightSaving::None),
+            "America/Denver" | "America/Edmonton" | "America/Boise" | "US/Mountain" => (-7, 0, NorthAmerica),
+            "America/Phoenix" | "US/Arizona" => (-7, 0, DaylightSaving::None),
+            "America/Los_Angeles" | "America/Vancouver" | "US/Pacific" => (-8, 0, NorthAmerica),
+            "America/Anchorage" | "America/Juneau" | "US/Alaska" => (-9, 0, NorthAmerica),
+            "Pacific/Honolulu" | "US/Hawaii" => (-10, 0, DaylightSaving::None),
+            "America/Mexico_City" => (-6, 0, DaylightSaving::None),
+            "America/Bogota" | "America/Lima" => (-5, 0, DaylightSaving::None),
+            "America/Sao_Paulo" | "America/Argentina/Buenos_Aires" => (-3, 0, DaylightSaving::None),
+            "Europe/London" | "Europe/Dublin" | "Europe/Lisbon" => (0, 0, Europe),
+            "Europe/Paris" | "Europe/Berlin" | "Europe/Madrid" | "Europe/Rome" | "Europe/Amsterdam" |
+            "Europe/Brussels" | "Europe/Vienna" | "Europe/Stockholm" | "Europe/Oslo" | "Europe/Copenhagen" |
+            "Europe/Warsaw" | "Europe/Prague" | "Europe/Zurich" | "Europe/Budapest" => (1, 0, Europe),
+            "Europe/Helsinki" | "Europe/Athens" | "Europe/Kiev" | "Europe/Kyiv" | "Europe/Bucharest" |
+            "Europe/Sofia" | "Europe/Riga" | "Europe/Vilnius" | "Europe/Tallinn" => (2, 0, Europe),
+            "Europe/Istanbul" | "Europe/Moscow" | "Asia/Riyadh" => (3, 0, DaylightSaving::None),
+            "Asia/Dubai" => (4, 0, DaylightSaving::None),
+            "Asia/Karachi" => (5, 0, DaylightSaving::None),
+            "Asia/Kolkata" | "Asia/Calcutta" => (5, 30, DaylightSaving::None),
+            "Asia/Bangkok" | "Asia/Jakarta" => (7, 0, DaylightSaving::None),
+            "Asia/Shanghai" | "Asia/Hong_Kong" | "Asia/Singapore" | "Asia/Taipei" | "Australia/Perth" => {
+                (8, 0, DaylightSaving::None)
+            }
+            "Asia/Tokyo" | "Asia/Seoul" => (9, 0, DaylightSaving::None),
+            "Australia/Brisbane" => (10, 0, DaylightSaving::None),
+            _ => return None,
+        };
+        Some(TimeZone { standard_offset: hours * 3600 + minutes * 60, daylight_saving })
+    }
+
+    /// Seconds east of UTC at an instant.
+    pub fn offset_at(&self, utc: &DateTime) -> i64 {
+        let seconds = utc.to_unix_seconds();
+        let daylight = match self.daylight_saving {
+            DaylightSaving::None => false,
+            DaylightSaving::NorthAmerica => {
+                let start = nth_sunday(utc.year, 3, 2) * 86_400 + 2 * 3600 - self.standard_offset;
+                let end = nth_sunday(utc.year, 11, 1) * 86_400 + 3600 - self.standard_offset;
+                start <= seconds && seconds < end
+            }
+            DaylightSaving::Europe => {
+                let start = last_sunday(utc.year, 3) * 86_400 + 3600;
+                let end = last_sunday(utc.year, 10) * 86_400 + 3600;
+                start <= seconds && seconds < end
+            }
+        };
+        if daylight {
+            self.standard_offset + 3600
+        } else {
+            self.standard_offset
+        }
+    }
+
+    /// The local time at a UTC instant.
+    pub fn to_local(&self, utc: &DateTime) -> DateTime {
+        DateTime::from_unix_seconds(utc.to_unix_seconds() + self.offset_at(utc))
+    }
+
+    /// The UTC instant of a local time. A time skipped when the clocks go
+    /// forward is read with the offset before the change; one repeated when
+    /// they go back, as the first of the two.
+    pub fn to_utc(&self, local: &DateTime) -> DateTime {
+        let seconds = local.to_unix_seconds();
+        let daylight = DateTime::from_unix_seconds(seconds - self.standard_offset - 3600);
+        if self.offset_at(&daylight) == self.standard_offset + 3600 {
+            daylight
+        } else {
+            DateTime::from_unix_seconds(seconds - self.standard_offset)
+        }
+    }
+}
+
+/// Days since 1970-01-01 of the `n`th Sunday in a month.
+fn nth_sunday(year: i64, month: u32, n: i64) -> i64 {
+    let first = DateTime { year, month, day: 1, hour: 0, minute: 0, second: 0 };
+    first.days_since_epoch() + (7 - i64::from(first.weekday())) % 7 + 7 * (n - 1)
+}
+
+/// Days since 1970-01-01 of the last Sunday in a month of 31 days.
+fn last_sunday(year: i64, month: u32) -> i64 {
+    let last = DateTime { year, month, day: 31, hour: 0, minute: 0, second: 0 };
+    last.days_since_epoch() - i64::from(last.weekday())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeZone;
+    use common::DateTime;
+
+    fn date(text: &str) -> DateTime {
+        DateTime::parse(text).unwrap()
+    }
+
+    #[test]
+    fn converts_across_daylight_saving() {
+        let denver = TimeZone::named("America/Denver").unwrap();
+        assert_eq!(denver.to_local(&date("2016-03-19T05:59:59")), date("2016-03-18T23:59:59"));
+        assert_eq!(denver.to_local(&date("2016-01-16T06:59:59")), date("2016-01-15T23:59:59"));
+        assert_eq!(denver.to_local(&date("2016-03-13T08:59:59")), date("2016-03-13T01:59:59"));
+        assert_eq!(denver.to_local(&date("2016-03-13T09:00:00")), date("2016-03-13T03:00:00"));
+        assert_eq!(denver.to_local(&date("2016-11-06T07:59:59")), date("2016-11-06T01:59:59"));
+        assert_eq!(denver.to_local(&date("2016-11-06T08:00:00")), date("2016-11-06T01:00:00"));
+        assert_eq!(denver.to_utc(&date("2016-03-18T23:59:59")), date("2016-03-19T05:59:59"));
+        assert_eq!(denver.to_utc(&date("2016-01-15T23:59:59")), date("2016-01-16T06:59:59"));
+
+        let paris = TimeZone::named("Europe/Paris").unwrap();
+        assert_eq!(paris.to_local(&date("2016-03-27T00:59:59")), date("2016-03-27T01:59:59"));
+        assert_eq!(paris.to_local(&date("2016-03-27T01:00:00")), date("2016-03-27T03:00:00"));
+        assert_eq!(paris.to_utc(&date("2016-10-30T12:00:00")), date("2016-10-30T11:00:00"));
+
+        assert_eq!(TimeZone::named("Asia/Kolkata").unwrap().to_local(&date("2016-03-18T20:00:00")),
+                   date("2016-03-19T01:30:00"));
+        assert_eq!(TimeZone::named("Mars/Olympus_Mons"), None);
+    }
+}
@@ -0,0 +1,103 @@
+/// One timed piece of a caption track. Times are in milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start: u64,
+    pub end: u64,
+    pub text: String,
+}
+
+/// Reads the cues of an SRT or WebVTT track. Headers, comments, styles and
+/// cues with unreadable timings are skipped.
+pub fn parse(content: &str) -> Vec<Cue> {
+    let content = content.replace("\r\n", "\n").replace('\r', "\n");
+    let mut cues = Vec::new();
+    for block in content.split("\n\n") {
+        let lines: Vec<&str> = block.lines().collect();
+        let timing = match lines.iter().position(|line| line.contains("-->")) {
+            Some(timing) => timing,
+            None => continue,
+        };
+        let mut times = lines[timing].split("-->");
+        let start = times.next().and_then(parse_time);
+        let end = times.next().and_then(|end| end.split_whitespace().next()).and_then(parse_time);
+        if let (Some(start), Some(end)) = (start, end) {
+            let text = lines[timing + 1..].join("\n");
+            cues.push(Cue { start, end, text: text.trim().to_string() });
+        }
+    }
+    cues
+}
+
+/// `HH:MM:SS,mmm` as SRT writes it, or `[HH:]MM:SS.mmm` as WebVTT does.
+fn parse_time(text: &str) -> Option<u64> {
+    let text = text.trim().replace(',', ".");
+    let (clock, millis) = match text.find('.') {
+        Some(dot) => (&text[..dot], &text[dot + 1..]),
+        None => (text.as_str(), "0"),
+    };
+    let millis: u64 = format!("{:0<3}", millis).get(..3)?.parse().ok()?;
+    let mut seconds = 0;
+    let parts: Vec<&str> = clock.split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    for part in parts {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(seconds * 1000 + millis)
+}
+
+fn format_time(millis: u64, separator: char) -> String {
+    format!("{:02}:{:02}:{:02}{}{:03}",
+            millis / 3_600_000,
+            millis / 60_000 % 60,
+            millis / 1000 % 60,
+            separator,
+            millis % 1000)
+}
+
+pub fn to_vtt(cues: &[Cue]) -> String {
+    let mut vtt = "WEBVTT\n".to_string();
+    for cue in cues {
+        vtt.push_str(&format!("\n{} --> {}\n{}\n", format_time(cue.start, '.'), format_time(cue.end, '.'), cue.text));
+    }
+    vtt
+}
+
+pub fn to_srt(cues: &[Cue]) -> String {
+    let mut srt = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        if i > 0 {
+            srt.push('\n');
+        }
+        srt.push_str(&format!("{}\n{} --> {}\n{}\n",
+                              i + 1,
+                              format_time(cue.start, ','),
+                              format_time(cue.end, ','),
+                              cue.text));
+    }
+    srt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, to_srt, to_vtt, Cue};
+
+    #[test]
+    fn converts_srt_to_vtt() {
+        let cues = parse("1\r\n00:00:01,500 --> 00:00:04,000\r\nHello,\r\nworld\r\n\r\n2\r\n00:01:02,000 --> 00:01:03,250\r\nBye\r\n");
+        assert_eq!(cues, vec![
+            Cue { start: 1500, end: 4000, text: "Hello,\nworld".to_string() },
+            Cue { start: 62000, end: 63250, text: "Bye".to_string() },
+        ]);
+        assert_eq!(to_vtt(&cues), "WEBVTT\n\n00:00:01.500 --> 00:00:04.000\nHello,\nworld\n\n00:01:02.000 --> 00:01:03.250\nBye\n");
+    }
+
+    #[test]
+    fn converts_vtt_to_srt() {
+        let cues = parse("WEBVTT - Lecture 1\n\nNOTE edited by hand\n\nintro\n00:01.5 --> 00:04.000 align:start\nHello\n");
+        assert_eq!(cues, vec![Cue { start: 1500, end: 4000, text: "Hello".to_string() }]);
+        assert_eq!(to_srt(&cues), "1\n00:00:01,500 --> 00:00:04,000\nHello\n");
+        assert_eq!(parse("00:00:01,000 --> soon\nHello\n"), vec![]);
+    }
+}
@@ -0,0 +1,300 @@
+use common::{DateTime, Outcome, OutcomeGroup, Summary};
+use export::strip_tags;
+use std::collections::BTreeMap;
+use summarize::json::Json;
+
+/// The RFC 4122 namespace for URLs, which every identifier here is derived
+/// under.
+const URL_NAMESPACE: [u8; 16] = [0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad, 0x11, 0xd1,
+                                 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];
+
+/// The course's learning outcomes as an IMS CASE 1.0 `CFPackage`. Groups
+/// and outcomes become `CFItem`s joined by `isChildOf` associations.
+/// Identifiers are name-based UUIDs of the cartridge identifiers, so
+/// exporting the same cartridge twice gives the same identifiers. URIs are
+/// built from `base_uri`, e.g. `https://case.example.org/ims/case/v1p0`.
+/// Every `lastChangeDateTime` is `last_change`, taken as UTC, so the package
+/// depends only on its arguments.
+pub fn package(summary: &Summary, base_uri: &str, last_change: &DateTime) -> String {
+    let base_uri = base_uri.trim_end_matches('/');
+    let now = format!("{}+00:00", last_change);
+    let document_id = uuid_v5(&summary.identifier);
+    let title = summary.general.title.value();
+    let document_link = link(title, &document_id, &format!("{}/CFDocuments/{}", base_uri, document_id));
+
+    let mut document = BTreeMap::new();
+    document.insert("identifier", string(&document_id));
+    document.insert("uri", string(&format!("{}/CFDocuments/{}", base_uri, document_id)));
+    document.insert("title", string(title));
+    document.insert("creator", string(creator(summary)));
+    document.insert("lastChangeDateTime", string(&now));
+    document.insert("CFPackageURI", link(title, &document_id, &format!("{}/CFPackages/{}", base_uri, document_id)));
+    if let Some(language) = summary.metadata.general.languages.first() {
+        document.insert("language", string(language));
+    }
+
+    let mut package = CasePackage {
+        summary_identifier: &summary.identifier,
+        base_uri,
+        now: &now,
+        document_link,
+        items: Vec::new(),
+        associations: Vec::new(),
+    };
+    let root = package.document_link.clone();
+    package.add_group_children(&summary.outcomes, &root);
+
+    let mut json = BTreeMap::new();
+    json.insert("CFDocument", object(document));
+    json.insert("CFItems", Json::Array(package.items));
+    json.insert("CFAssociations", Json::Array(package.associations));
+    object(json).to_string()
+}
+
+struct CasePackage<'a> {
+    summary_identifier: &'a str,
+    base_uri: &'a str,
+    now: &'a str,
+    document_link: Json,
+    items: Vec<Json>,
+    associations: Vec<Json>,
+}
+
+impl<'a> CasePackage<'a> {
+    fn add_group_children(&mut self, group: &OutcomeGroup, parent: &Json) {
+        let mut sequence = 0;
+        for outcome in &group.outcomes {
+            sequence += 1;
+            let item = self.add_outcome(outcome);
+            self.add_child_of(&item, parent, sequence);
+        }
+        for child in &group.groups {
+            sequence += 1;
+            let item = self.add_item(&child.identifier, &child.title, child.description.as_deref(),
+                                     child.vendor_guid.as_deref(), "Outcome Group");
+            self.add_child_of(&item, parent, sequence);
+            self.add_group_children(child, &item);
+        }
+    }
+
+    fn add_outcome(&mut self, outcome: &Outcome) -> Json {
+        self.add_item(&outcome.identifier, &outcome.title, outcome.description.as_deref(),
+                      outcome.vendor_guid.as_deref(), "Outcome")
+    }
+
+    /// Adds a `CFItem` and returns a link to it.
+    fn add_item(&mut self, identifier: &str, title: &str, description: Option<&str>, code: Option<&str>,
+                item_type: &str) -> Json {
+        let id = uuid_v5(&format!("{}/{}", self.summary_identifier, identifier));
+        let uri = format!("{}/CFItems/{}", self.base_uri, id);
+        let mut item = BTreeMap::new();
+        item.insert("identifier", string(&id));
+        item.insert("uri", string(&uri));
+        item.insert("fullStatement", string(title));
+        item.insert("CFItemType", string(item_type));
+        item.insert("lastChangeDateTime", string(self.now));
+        item.insert("CFDocumentURI", self.document_link.clone());
+        if let Some(notes) = description.map(strip_tags).filter(|notes| !notes.is_empty()) {
+            item.insert("notes", string(&notes));
+        }
+        if let Some(code) = code.filter(|code| !code.is_empty()) {
+            item.insert("humanCodingScheme", string(code));
+        }
+        self.items.push(object(item));
+        link(title, &id, &uri)
+    }
+
+    fn add_child_of(&mut self, child: &Json, parent: &Json, sequence: u32) {
+        let child_id = child.get("identifier").and_then(Json::as_str).unwrap_or("");
+        let parent_id = parent.get("identifier").and_then(Json::as_str).unwrap_or("");
+        let id = uuid_v5(&format!("{}/isChildOf/{}", child_id, parent_id));
+        let mut association = BTreeMap::new();
+        association.insert("identifier", string(&id));
+        association.insert("uri", string(&format!("{}/CFAssociations/{}", self.base_uri, id)));
+        association.insert("associationType", string("isChildOf"));
+        association.insert("sequenceNumber", Json::Number(f64::from(sequence)));
+        association.insert("originNodeURI", child.clone());
+        association.insert("destinationNodeURI", parent.clone());
+        association.insert("lastChangeDateTime", string(self.now));
+        association.insert("CFDocumentURI", self.document_link.clone());
+        self.associations.push(object(association));
+    }
+}
+
+/// The first contributor named in the manifest metadata, or the course title.
+fn creator(summary: &Summary) -> &str {
+    summary.metadata.life_cycle.contributors
+        .iter()
+        .flat_map(|contributor| contributor.entities.iter())
+        .filter_map(|entity| entity.formatted_name.as_deref().or(entity.organization.as_deref()))
+        .next()
+        .unwrap_or_else(|| summary.general.title.value())
+}
+
+/// A CASE `LinkURI` object.
+fn link(title: &str, identifier: &str, uri: &str) -> Json {
+    let mut link = BTreeMap::new();
+    link.insert("title", string(title));
+    link.insert("identifier", string(identifier));
+    link.insert("uri", string(uri));
+    object(link)
+}
+
+fn string(value: &str) -> Json {
+    Json::String(value.to_string())
+}
+
+fn object(map: BTreeMap<&str, Json>) -> Json {
+    Json::Object(map.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+}
+
+/// A name-based (version 5) UUID in the URL namespace.
+fn uuid_v5(name: &str) -> String {
+    name_based_uuid(&URL_NAMESPACE, name)
+}
+
+fn name_based_uuid(namespace: &[u8; 16], name: &str) -> String {
+    let mut data = namespace.to_vec();
+    data.extend_from_slice(name.as_bytes());
+    let mut bytes = sha1(&data);
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: Vec<String> = bytes[..16].iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", hex[..4].concat(), hex[4..6].concat(), hex[6..8].concat(), hex[8..10].concat(),
+            hex[10..16].concat())
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476, 0xc3d2_e1f0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([chunk[4 * i], chunk[4 * i + 1], chunk[4 * i + 2], chunk[4 * i + 3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let (mut a, mut b, mut c, mut d, mut e) = (h[0], h[1], h[2], h[3], h[4]);
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+                _ => (b ^ c ^ d, 0xca62_c1d6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in h.iter_mut().zip(&[a, b, c, d, e]) {
+            *state = state.wrapping_add(*value);
+        }
+    }
+    let mut digest = [0u8; 20];
+    for (i, word) in h.iter().enumerate() {
+        digest[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{name_based_uuid, package, sha1, uuid_v5};
+    use common::{DateTime, LangString, ManifestBuilder, Outcome, OutcomeGroup, Summary};
+    use summarize::json::Json;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn hashes_fips_180_vectors() {
+        assert_eq!(hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(hex(&sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+                   "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+        assert_eq!(hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+    }
+
+    #[test]
+    fn derives_name_based_uuids() {
+        // The RFC 4122 DNS namespace, and the example in Python's `uuid` docs.
+        let dns = [0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];
+        assert_eq!(name_based_uuid(&dns, "python.org"), "886313e1-3b8a-5372-9b90-0c9aee199e5d");
+        assert_eq!(uuid_v5("http://www.example.com/"), "fcde3c85-2270-590f-9e7c-ee003d65e0e2");
+    }
+
+    fn summary() -> Summary {
+        let mut summary = Summary::new(ManifestBuilder::new().finalize());
+        summary.identifier = "i3ebf4e9fe673c98a2e10715ec293b6bf".to_string();
+        let mut title = LangString::new();
+        title.insert(None, "Tommy's Awesome Course".to_string());
+        summary.general.title = title;
+        let mut outcome = Outcome::new(Some("i69aed9fc9ce7e3161c8225fa85a328b4".to_string()));
+        outcome.title = "Example Outcome".to_string();
+        outcome.description = Some("<p>Lorem &amp; ipsum</p>".to_string());
+        outcome.vendor_guid = Some("MATH.1".to_string());
+        let mut group = OutcomeGroup::new(Some("i26c96a8374d7a3d78f7c874f9ee513e3".to_string()));
+        group.title = "Example Outcome Group".to_string();
+        group.outcomes.push(outcome);
+        summary.outcomes.groups.push(group);
+        summary
+    }
+
+    fn last_change() -> DateTime {
+        DateTime::parse("2016-03-15T12:00:00").unwrap()
+    }
+
+    #[test]
+    fn exports_package() {
+        let json = Json::parse(&package(&summary(), "https://case.example.org/ims/case/v1p0/", &last_change())).unwrap();
+        let document = json.get("CFDocument").unwrap();
+        assert_eq!(document.get("title").and_then(Json::as_str), Some("Tommy's Awesome Course"));
+        let document_id = document.get("identifier").and_then(Json::as_str).unwrap();
+        assert_eq!(document_id, uuid_v5("i3ebf4e9fe673c98a2e10715ec293b6bf"));
+        assert_eq!(document.get("lastChangeDateTime").and_then(Json::as_str), Some("2016-03-15T12:00:00+00:00"));
+        assert_eq!(document.get("uri").and_then(Json::as_str),
+                   Some(format!("https://case.example.org/ims/case/v1p0/CFDocuments/{}", document_id).as_str()));
+        let package_uri = document.get("CFPackageURI").unwrap();
+        assert_eq!(package_uri.get("identifier").and_then(Json::as_str), Some(document_id));
+        assert_eq!(package_uri.get("uri").and_then(Json::as_str),
+                   Some(format!("https://case.example.org/ims/case/v1p0/CFPackages/{}", document_id).as_str()));
+
+        let items = json.get("CFItems").and_then(Json::as_array).unwrap();
+        assert_eq!(items.len(), 2);
+        let outcome = &items[1];
+        assert_eq!(outcome.get("fullStatement").and_then(Json::as_str), Some("Example Outcome"));
+        assert_eq!(outcome.get("CFItemType").and_then(Json::as_str), Some("Outcome"));
+        assert_eq!(outcome.get("humanCodingScheme").and_then(Json::as_str), Some("MATH.1"));
+        assert_eq!(outcome.get("notes").and_then(Json::as_str), Some("Lorem & ipsum"));
+
+        let associations = json.get("CFAssociations").and_then(Json::as_array).unwrap();
+        assert_eq!(associations.len(), 2);
+        let group_id = items[0].get("identifier").and_then(Json::as_str);
+        let destination = |i: usize| associations[i].get("destinationNodeURI").and_then(|node| node.get("identifier"));
+        assert_eq!(destination(0).and_then(Json::as_str), Some(document_id));
+        assert_eq!(destination(1).and_then(Json::as_str), group_id);
+    }
+
+    #[test]
+    fn identifiers_are_stable() {
+        let first = Json::parse(&package(&summary(), "https://case.example.org", &last_change())).unwrap();
+        let second = Json::parse(&package(&summary(), "https://case.example.org", &last_change())).unwrap();
+        let ids = |json: &Json| -> Vec<String> {
+            json.get("CFItems").and_then(Json::as_array).unwrap()
+                .iter()
+                .filter_map(|item| item.get("identifier").and_then(Json::to_key))
+                .collect()
+        };
+        assert_eq!(ids(&first), ids(&second));
+        assert_eq!(first.to_string(), second.to_string());
+    }
+}
@@ -0,0 +1,119 @@
+use analysis::{Timeline, TimelineKind};
+use common::DateTime;
+use export::strip_tags;
+
+/// An iCalendar document with one `VEVENT` per timeline entry. Canvas dates
+/// are UTC, so times are written with a `Z`.
+pub fn calendar(timeline: &Timeline, name: &str) -> String {
+    let stamp = DateTime::now().to_ics();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//comcart//Course Timeline//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape(name)),
+    ];
+    for entry in &timeline.entries {
+        lines.push("BEGIN:VEVENT".to_string());
+        let kind = format!("{:?}", entry.kind).to_lowercase();
+        lines.push(format!("UID:{}-{}@comcart", entry.identifier, kind));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        if entry.all_day {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", &entry.at.to_ics()[..8]));
+        } else {
+            lines.push(format!("DTSTART:{}", entry.at.to_ics()));
+            if let Some(end) = entry.end {
+                lines.push(format!("DTEND:{}", end.to_ics()));
+            }
+        }
+        let summary = match entry.kind {
+            TimelineKind::Event => entry.title.clone(),
+            kind => format!("{}: {}", kind.label(), entry.title),
+        };
+        lines.push(format!("SUMMARY:{}", escape(&summary)));
+        if let Some(description) = entry.description.as_deref().map(strip_tags).filter(|text| !text.is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape(&description)));
+        }
+        if !entry.published {
+            lines.push("STATUS:TENTATIVE".to_string());
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect::<Vec<_>>().concat()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits a content line into 75-octet pieces, without breaking a
+/// character, and ends it with CRLF.
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calendar, fold};
+    use analysis::{Timeline, TimelineEntry, TimelineKind};
+    use common::DateTime;
+
+    fn entry(kind: TimelineKind, title: &str) -> TimelineEntry {
+        TimelineEntry {
+            at: DateTime::parse("2016-03-01T18:00:00").unwrap(),
+            end: DateTime::parse("2016-03-01T19:00:00"),
+            all_day: false,
+            kind,
+            identifier: "i1".to_string(),
+            title: title.to_string(),
+            description: Some("<p>Bring notes; and snacks</p>".to_string()),
+            published: true,
+        }
+    }
+
+    #[test]
+    fn writes_events() {
+        let mut due = entry(TimelineKind::Due, "Essay, part 1");
+        due.end = None;
+        due.description = None;
+        due.published = false;
+        let timeline = Timeline { entries: vec![entry(TimelineKind::Event, "Review"), due] };
+        let ics = calendar(&timeline, "Course");
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        assert!(lines.contains(&"UID:i1-event@comcart"));
+        assert!(lines.contains(&"DTSTART:20160301T180000Z"));
+        assert!(lines.contains(&"DTEND:20160301T190000Z"));
+        assert!(lines.contains(&"SUMMARY:Review"));
+        assert!(lines.contains(&"DESCRIPTION:Bring notes\\; and snacks"));
+        assert!(lines.contains(&"SUMMARY:Due: Essay\\, part 1"));
+        assert!(lines.contains(&"STATUS:TENTATIVE"));
+        assert_eq!(lines.iter().filter(|line| **line == "BEGIN:VEVENT").count(), 2);
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let folded = fold(&format!("SUMMARY:{}", "é".repeat(40)));
+        let lines: Vec<&str> = folded.trim_end().split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert!(lines[1].starts_with(' '));
+    }
+}
@@ -1,24 +1,58 @@
+// lazy_static 0.2 expands to the deprecated `ONCE_INIT` at every `lazy_static!`.
+#![allow(deprecated)]
+
 #[macro_use] extern crate lazy_static;
 extern crate regex;
 extern crate xml;
 extern crate zip;
 
-use std::io::{Result, BufReader, Read, Seek};
+use std::io::{Result, BufReader, Read, Seek, Write};
+use std::path::Path;
 
 mod summarize;
-mod common;
+pub mod analysis;
+pub mod common;
+pub mod export;
+pub mod rewrite;
+pub mod source;
 
 pub fn process<R: Read + Seek>(reader: BufReader<R>) -> Result<common::Summary> {
-    let zip_file = try!(zip::ZipArchive::new(reader));
-    let summary = try!(summarize::summarize(zip_file));
-    Ok(summary)
+    process_source(&mut source::ZipSource::new(reader)?)
+}
+
+/// Summarizes a cartridge that has been extracted to a directory.
+pub fn process_directory<P: AsRef<Path>>(path: P) -> Result<common::Summary> {
+    process_source(&mut source::DirectorySource::new(path))
+}
+
+/// Summarizes a cartridge read from any source: a zip, a directory, or
+/// entries in memory.
+pub fn process_source<S: source::CartridgeSource>(source: &mut S) -> Result<common::Summary> {
+    summarize::summarize(source)
+}
+
+/// Writes a copy of the cartridge with every date moved by `shift`.
+pub fn shift_dates<R: Read + Seek, W: Write + Seek>(reader: BufReader<R>, writer: W, shift: &rewrite::DateShift)
+                                                   -> Result<W> {
+    shift_source_dates(&mut source::ZipSource::new(reader)?, writer, shift)
+}
+
+/// Writes a zipped copy of a cartridge read from any source, with every
+/// date moved by `shift`.
+pub fn shift_source_dates<S: source::CartridgeSource, W: Write + Seek>(source: &mut S, writer: W,
+                                                                      shift: &rewrite::DateShift) -> Result<W> {
+    shift.shift_cartridge(source, writer)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::process;
-    use std::fs::File;
-    use std::io::{BufReader};
+    use super::{process, process_directory, shift_dates};
+    use common::{DateTime, Exporter, ItemType};
+    use rewrite::DateShift;
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::{BufReader, Cursor, ErrorKind, Read, Write};
+    use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
     fn get_zip_file() -> File {
         if let Ok(file) = File::open("test.imscc") {
@@ -34,14 +68,200 @@ mod tests {
         let file = get_zip_file();
         match process(BufReader::new(file)) {
             Ok(summary) => {
-                assert_eq!(summary.general.title, "Tommy's Awesome Course");
-                assert_eq!(summary.general.copyright, "Private (Copyrighted) - http://en.wikipedia.org/wiki/Copyright");
-                assert_eq!(summary.general.description, "");
+                assert_eq!(summary.general.title.value(), "Tommy's Awesome Course");
+                assert_eq!(summary.exporter, Exporter::Canvas);
+                assert_eq!(summary.general.copyright.value(), "Private (Copyrighted) - http://en.wikipedia.org/wiki/Copyright");
+                assert_eq!(summary.general.description.value(), "");
                 assert_eq!(summary.modules.len(), 105);
+                let lor_debug = &summary.modules[0].items[0];
+                assert_eq!(lor_debug.title, "lor debug");
+                assert!(!lor_debug.published);
+                assert_eq!(lor_debug.content_type, Some("ContextExternalTool".to_string()));
+                assert!(lor_debug.new_tab);
+                assert_eq!(lor_debug.item_type, ItemType::ExternalTool);
+                let example_module = &summary.modules[2];
+                assert_eq!(example_module.items.len(), 7);
+                assert_eq!(example_module.items[4].item_type, ItemType::SubHeader);
+                assert_eq!(example_module.items[6].item_type, ItemType::ExternalUrl);
+                assert_eq!(summary.modules[2].completion_requirements.len(), 6);
+                assert!(summary.modules[2].require_sequential_progress);
+                let settings = summary.course_settings.as_ref().unwrap();
+                assert_eq!(settings.course_code, Some("666-13".to_string()));
+                assert_eq!(settings.license, Some("private".to_string()));
+                let report = summary.prerequisite_graph().report();
+                assert!(report.cycles.is_empty());
+                assert!(report.requirement_problems.is_empty());
+                assert_eq!(summary.assignment_groups.len(), 9);
+                assert_eq!(summary.assignments.len(), 232);
+                let standard = summary.course_grading_standard().unwrap();
+                assert_eq!(standard.title, "Example Course Grading Scheme");
+                assert_eq!(standard.letter_for(88.0), Some("B+"));
+                let letter_graded = summary.assignments
+                    .iter()
+                    .find(|assignment| assignment.identifier == "i8b88a298b86aebce3753f42f0e09e59b")
+                    .unwrap();
+                assert_eq!(summary.assignment_grading_standard(letter_graded), Some(standard));
+                assert_eq!(summary.rubrics.len(), 1);
+                assert_eq!(summary.rubrics[0].criteria[1].ratings.len(), 4);
+                let example_outcome = summary.outcomes.find_outcome("i69aed9fc9ce7e3161c8225fa85a328b4").unwrap();
+                assert_eq!(example_outcome.mastery_points, Some(4.0));
+                let alignments = summary.outcome_alignment_report();
+                assert_eq!(alignments.outcomes[0].rubrics, vec!["ibff36139d637040dc9402090a3ec75f8"]);
+                assert!(summary.outcomes_to_case("https://case.example.org", &DateTime::now()).contains("\"fullStatement\":\"Example Outcome\""));
+                let timeline = summary.timeline();
+                assert!(timeline.entries.iter().any(|entry| entry.title == "Example Event"));
+                assert!(timeline.to_ics("Course").contains("SUMMARY:Example Event\r\n"));
+                let captions = summary.caption_report();
+                assert_eq!(captions.media.len(), 1);
+                assert_eq!(captions.uncaptioned()[0].href, "web_resources/sample_mpeg4.mp4");
+                assert_eq!(summary.files.files().len(), 3);
+                let doc = summary.files.file("web_resources/Example Folder/Example.doc").unwrap();
+                assert_eq!((doc.size, doc.compressed_size, doc.crc32), (26624, 7124, 0xaa43_fa70));
+                assert_eq!(doc.identifier.as_deref(), Some("i97521b4eda791dd993051a519e424e33"));
+                assert_eq!(summary.files.root.size(), 39979 + 245779 + 26624);
+                assert!(summary.restricted_file_links().is_empty());
+                let syllabus = summary.syllabus_resource().unwrap();
+                assert_eq!(syllabus.href.as_deref(), Some("course_settings/syllabus.html"));
+                assert!(summary.syllabus_html.is_some());
+                assert_eq!(summary.syllabus(), None);
+                let report = summary.grade_calculator().calculate(&Default::default());
+                assert_eq!(report.final_percent, None);
+
+                let student_view = summary.student_view();
+                assert_eq!(student_view.modules.len(), 105);
+                assert!(student_view.modules[0].items.is_empty());
             }
             Err(e) => {
-                panic!(e)
+                panic!("{}", e)
+            }
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn test_shift_dates() {
+        let mut shifted = shift_dates(BufReader::new(get_zip_file()), Cursor::new(Vec::new()), &DateShift::by_days(7))
+            .unwrap();
+        shifted.set_position(0);
+        let summary = process(BufReader::new(shifted)).unwrap();
+        assert_eq!(summary.events[0].start_at.as_deref(), Some("2014-04-29T18:00:00"));
+        assert_eq!(summary.events[0].end_at.as_deref(), Some("2014-04-29T19:00:00"));
+        assert_eq!(summary.assignments.len(), 232);
+    }
+
+    fn get_fixture(name: &str) -> File {
+        match File::open(format!("fixtures/{}", name)) {
+            Ok(file) => file,
+            Err(e) => panic!("Could not open fixture '{}': {}", name, e),
+        }
+    }
+
+    #[test]
+    fn test_moodle_fixture() {
+        let summary = process(BufReader::new(get_fixture("moodle.imscc"))).unwrap();
+        assert_eq!(summary.exporter, Exporter::Moodle);
+        assert_eq!(summary.general.title.value(), "Introduction to Botany");
+        let titles: Vec<&str> = summary.modules.iter().map(|module| module.title.as_str()).collect();
+        assert_eq!(titles, vec!["General", "Week 1: Seeds", "Week 2: Roots"]);
+
+        let general = &summary.modules[0].items;
+        assert_eq!(general.len(), 3);
+        assert_eq!(general[0].item_type, ItemType::DiscussionTopic);
+        assert_eq!(general[1].title, "Read this before the first class");
+        assert_eq!(general[1].item_type, ItemType::SubHeader);
+        assert_eq!(general[2].item_type, ItemType::WebLink);
+        assert_eq!(general[2].url.as_deref(), Some("https://botany.org/"));
+
+        let week_1 = &summary.modules[1].items;
+        let outline: Vec<(&str, u32)> = week_1.iter().map(|item| (item.title.as_str(), item.indent)).collect();
+        assert_eq!(outline, vec![("How seeds germinate", 0), ("Readings", 0), ("seed-anatomy.pdf", 1), ("dormancy.pdf", 1)]);
+        assert_eq!(week_1[1].item_type, ItemType::SubHeader);
+        assert_eq!(week_1[2].item_type, ItemType::WebContent);
+
+        let week_2 = &summary.modules[2].items;
+        assert_eq!(week_2[0].item_type, ItemType::Assessment);
+        assert_eq!(week_2[1].item_type, ItemType::ExternalTool);
+    }
+
+    #[test]
+    fn test_blackboard_fixture() {
+        let summary = process(BufReader::new(get_fixture("blackboard.imscc"))).unwrap();
+        assert_eq!(summary.exporter, Exporter::Blackboard);
+        let titles: Vec<&str> = summary.modules.iter().map(|module| module.title.as_str()).collect();
+        assert_eq!(titles, vec!["Course Content", "Discussions"]);
+
+        let content = &summary.modules[0].items;
+        let outline: Vec<(&str, u32, &ItemType)> =
+            content.iter().map(|item| (item.title.as_str(), item.indent, &item.item_type)).collect();
+        assert_eq!(outline, vec![
+            ("Syllabus", 0, &ItemType::WebContent),
+            ("Unit 1", 0, &ItemType::SubHeader),
+            ("Lecture notes", 1, &ItemType::WebContent),
+            ("Lab safety", 1, &ItemType::SubHeader),
+            ("Safety video", 2, &ItemType::ExternalUrl),
+            ("Unit 1 Test", 0, &ItemType::Assessment),
+        ]);
+        assert_eq!(content[4].url.as_deref(), Some("https://videos.example.edu/lab-safety"));
+        let notes = summary.resources.iter().find(|resource| resource.identifier == "res00005").unwrap();
+        assert_eq!(notes.files, vec!["res00005/lecture-notes.html", "csfiles/home_dir/unit-1.pptx"]);
+        assert_eq!(summary.modules[1].items[0].item_type, ItemType::DiscussionTopic);
+    }
+
+    /// The Moodle fixture re-zipped inside each of `folders`, as happens when
+    /// a cartridge is extracted and compressed again, with its manifest
+    /// renamed.
+    fn wrapped_moodle_fixture(folders: &[&str], manifest: &str) -> Cursor<Vec<u8>> {
+        let mut archive = ZipArchive::new(get_fixture("moodle.imscc")).unwrap();
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).unwrap();
+            let name = match file.name() {
+                "imsmanifest.xml" => manifest.to_string(),
+                name => name.to_string(),
+            };
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents).unwrap();
+            for folder in folders {
+                zip.start_file(format!("{}{}", folder, name).as_str(), CompressionMethod::Deflated).unwrap();
+                zip.write_all(&contents).unwrap();
             }
         }
+        let mut cursor = zip.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn test_wrapped_manifest() {
+        let summary = process(BufReader::new(wrapped_moodle_fixture(&["Botany/"], "IMSManifest.xml"))).unwrap();
+        assert_eq!(summary.exporter, Exporter::Moodle);
+        assert_eq!(summary.modules.len(), 3);
+        assert_eq!(summary.modules[0].items[2].url.as_deref(), Some("https://botany.org/"));
+
+        let ambiguous = wrapped_moodle_fixture(&["Botany/", "Botany (1)/"], "imsmanifest.xml");
+        let error = process(BufReader::new(ambiguous)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_process_directory() {
+        let directory = env::temp_dir().join(format!("comcart-test-{}", std::process::id()));
+        let mut archive = ZipArchive::new(wrapped_moodle_fixture(&["Botany/"], "imsmanifest.xml")).unwrap();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).unwrap();
+            let path = directory.join(file.name());
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        let summary = process_directory(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+        let summary = summary.unwrap();
+        assert_eq!(summary.general.title.value(), "Introduction to Botany");
+        assert_eq!(summary.modules[1].items.len(), 4);
+
+        let error = process_directory(env::temp_dir().join("comcart-missing-directory")).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
     }
 }
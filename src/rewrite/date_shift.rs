@@ -0,0 +1,262 @@
+use common::{DateTime, EntryNames, TimeZone};
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+use source::CartridgeSource;
+use std::io::{Result, Seek, Write};
+use summarize::{course_settings, locate};
+
+/// Moves course dates to a new term, the way Canvas' course copy does: by
+/// a fixed offset or from an old start date to a new one, scaled to fit
+/// when both end dates are known, and then onto substitute weekdays.
+/// Days and weekdays are counted in the course's time zone, and local times
+/// of day are kept.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DateShift {
+    pub offset_days: i64,
+    pub old_start: Option<DateTime>,
+    pub new_start: Option<DateTime>,
+    pub old_end: Option<DateTime>,
+    pub new_end: Option<DateTime>,
+    /// Weekday to weekday, with Sunday as 0. A date that fell on a key
+    /// moves to the value's day in the week it lands in.
+    pub day_substitutions: BTreeMap<u32, u32>,
+    /// The zone dates are shifted in. When it's not set, `shift_cartridge`
+    /// takes the course's `time_zone` setting; otherwise it's UTC.
+    pub time_zone: Option<TimeZone>,
+}
+
+impl DateShift {
+    pub fn by_days(offset_days: i64) -> DateShift {
+        DateShift { offset_days, ..DateShift::default() }
+    }
+
+    pub fn between(old_start: DateTime, new_start: DateTime) -> DateShift {
+        DateShift {
+            old_start: Some(old_start),
+            new_start: Some(new_start),
+            ..DateShift::default()
+        }
+    }
+
+    pub fn ending(mut self, old_end: DateTime, new_end: DateTime) -> DateShift {
+        self.old_end = Some(old_end);
+        self.new_end = Some(new_end);
+        self
+    }
+
+    pub fn substitute(mut self, from_weekday: u32, to_weekday: u32) -> DateShift {
+        self.day_substitutions.insert(from_weekday, to_weekday);
+        self
+    }
+
+    pub fn in_time_zone(mut self, time_zone: TimeZone) -> DateShift {
+        self.time_zone = Some(time_zone);
+        self
+    }
+
+    /// Shifts a UTC date and time by local days.
+    pub fn shift(&self, date: DateTime) -> DateTime {
+        let time_zone = self.time_zone.unwrap_or_default();
+        time_zone.to_utc(&self.shift_local(time_zone.to_local(&date)))
+    }
+
+    fn shift_local(&self, date: DateTime) -> DateTime {
+        let old_day = date.days_since_epoch();
+        let new_day = match (self.old_start, self.new_start, self.old_end, self.new_end) {
+            (Some(old_start), Some(new_start), Some(old_end), Some(new_end))
+                if old_end.days_since_epoch() > old_start.days_since_epoch() =>
+            {
+                let old_span = (old_end.days_since_epoch() - old_start.days_since_epoch()) as f64;
+                let new_span = (new_end.days_since_epoch() - new_start.days_since_epoch()) as f64;
+                let progress = (old_day - old_start.days_since_epoch()) as f64 / old_span;
+                new_start.days_since_epoch() + (progress * new_span).round() as i64
+            }
+            (Some(old_start), Some(new_start), _, _) => {
+                old_day + new_start.days_since_epoch() - old_start.days_since_epoch()
+            }
+            _ => old_day + self.offset_days,
+        };
+        let mut shifted = date.add_days(new_day - old_day);
+        if let Some(&weekday) = self.day_substitutions.get(&date.weekday()) {
+            shifted = shifted.add_days(i64::from(weekday) - i64::from(shifted.weekday()));
+        }
+        shifted
+    }
+
+    /// Shifts a date as `DateTime::parse` reads it, keeping its form: a
+    /// bare date stays a date, and fractional seconds and a trailing `Z` are
+    /// kept. A bare date is already a local day, so it isn't converted.
+    /// Anything else is returned unchanged.
+    pub fn shift_text(&self, text: &str) -> String {
+        let date = match DateTime::parse(text) {
+            Some(date) => date,
+            None => return text.to_string(),
+        };
+        let text = text.trim();
+        let shifted = if text.contains('T') { self.shift(date) } else { self.shift_local(date) }.to_string();
+        let length = text.find(|c: char| !c.is_ascii_digit() && !['-', ':', 'T'].contains(&c)).unwrap_or(text.len());
+        format!("{}{}", &shifted[..length.min(shifted.len())], &text[length..])
+    }
+
+    /// Shifts the dates in an XML document. These are the contents of the
+    /// Canvas date elements `due_at`, `unlock_at`, `lock_at`,
+    /// `peer_reviews_due_at`, `start_at`, `end_at`, `conclude_at`,
+    /// `delayed_post_at`, `show_correct_answers_at`, `hide_correct_answers_at`,
+    /// `all_day_date` and `todo_date`, with or without attributes, written
+    /// `2016-03-20`, `2016-03-20T06:59:59`, or either of those with
+    /// fractional seconds and a `Z`. Other elements and text, and dates with
+    /// other offsets, are left alone.
+    pub fn shift_xml(&self, xml: &str) -> String {
+        lazy_static! {
+            static ref DATE_ELEMENT_R: Regex = Regex::new(
+                r"<(due_at|unlock_at|lock_at|peer_reviews_due_at|start_at|end_at|conclude_at|delayed_post_at|show_correct_answers_at|hide_correct_answers_at|all_day_date|todo_date)(\s[^>]*)?>(\d{4}-\d{2}-\d{2}(?:T\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?)?Z?)</"
+            ).unwrap();
+        }
+        DATE_ELEMENT_R.replace_all(xml, |captures: &Captures| {
+            let name = captures.at(1).unwrap_or("");
+            let attributes = captures.at(2).unwrap_or("");
+            let date = captures.at(3).unwrap_or("");
+            format!("<{}{}>{}</", name, attributes, self.shift_text(date))
+        })
+    }
+
+    /// Writes a copy of the cartridge with every date in its XML files
+    /// shifted, in the course's time zone unless one is set.
+    pub fn shift_cartridge<S: CartridgeSource, W: Write + Seek>(&self, source: &mut S, writer: W) -> Result<W> {
+        let time_zone = match self.time_zone {
+            Some(time_zone) => Some(time_zone),
+            None => course_time_zone(source)?,
+        };
+        let shift = DateShift { time_zone, ..self.clone() };
+        super::rewrite_cartridge(source, writer, |name, contents| {
+            if !name.ends_with(".xml") {
+                return None;
+            }
+            let xml = ::std::str::from_utf8(contents).ok()?;
+            let shifted = shift.shift_xml(xml);
+            if shifted == xml {
+                None
+            } else {
+                Some(shifted.into_bytes())
+            }
+        })
+    }
+}
+
+/// The zone named by the cartridge's `time_zone` course setting, if it has
+/// one and it's known.
+fn course_time_zone<S: CartridgeSource>(source: &mut S) -> Result<Option<TimeZone>> {
+    let names = source.entries()?;
+    let manifest_path = match locate::manifest_path(&names) {
+        Ok(path) => path,
+        Err(_) => return Ok(None),
+    };
+    let entries = EntryNames::within(locate::root(&manifest_path), &names);
+    let settings = match entries.find(course_settings::COURSE_SETTINGS_PATH) {
+        Some(name) => course_settings::parse(source.open(name)?),
+        None => return Ok(None),
+    };
+    Ok(settings.time_zone.and_then(|name| TimeZone::named(&name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateShift;
+    use common::{DateTime, TimeZone};
+    use source::{MemorySource, ZipSource};
+    use std::io::{Cursor, Read, Write};
+    use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+    fn date(text: &str) -> DateTime {
+        DateTime::parse(text).unwrap()
+    }
+
+    #[test]
+    fn shifts_by_offset_and_start_dates() {
+        assert_eq!(DateShift::by_days(7).shift_text("2016-02-26T06:59:59"), "2016-03-04T06:59:59");
+        let shift = DateShift::between(date("2015-08-24"), date("2016-01-11"));
+        assert_eq!(shift.shift_text("2015-08-26T18:00:00"), "2016-01-13T18:00:00");
+        assert_eq!(shift.shift_text("2015-09-01"), "2016-01-19");
+        assert_eq!(shift.shift_text("not a date"), "not a date");
+        assert_eq!(shift.shift_text("2015-08-26T18:00:00.000Z"), "2016-01-13T18:00:00.000Z");
+    }
+
+    #[test]
+    fn scales_to_new_term_length() {
+        let shift = DateShift::between(date("2015-08-24"), date("2016-01-11"))
+            .ending(date("2015-12-18"), date("2016-05-06"));
+        assert_eq!(shift.shift_text("2015-12-18T12:00:00"), "2016-05-06T12:00:00");
+        assert_eq!(shift.shift_text("2015-10-21T12:00:00"), "2016-03-09T12:00:00");
+    }
+
+    #[test]
+    fn substitutes_weekdays() {
+        // Monday classes move to Tuesday, Wednesday ones to Thursday.
+        let shift = DateShift::between(date("2015-08-24"), date("2016-01-11")).substitute(1, 2).substitute(3, 4);
+        assert_eq!(shift.shift_text("2015-08-24T10:00:00"), "2016-01-12T10:00:00");
+        assert_eq!(shift.shift_text("2015-08-26T10:00:00"), "2016-01-14T10:00:00");
+        assert_eq!(shift.shift_text("2015-08-28T10:00:00"), "2016-01-15T10:00:00");
+    }
+
+    #[test]
+    fn shifts_in_the_course_time_zone() {
+        // Due Friday 23:59 in Denver, which is already Saturday in UTC.
+        let shift = DateShift::by_days(7).substitute(5, 1).in_time_zone(TimeZone::named("America/Denver").unwrap());
+        assert_eq!(shift.shift_text("2016-03-19T05:59:59"), "2016-03-22T05:59:59");
+        assert_eq!(DateShift::by_days(7).substitute(5, 1).shift_text("2016-03-19T05:59:59"), "2016-03-26T05:59:59");
+        // The local time of day is kept across the change to daylight time.
+        assert_eq!(shift.shift_text("2016-03-10T06:59:59"), "2016-03-17T05:59:59");
+        assert_eq!(shift.shift_text("2016-03-18"), "2016-03-21");
+    }
+
+    #[test]
+    fn takes_the_time_zone_from_course_settings() {
+        let mut source = MemorySource::new();
+        source.insert("imsmanifest.xml", r#"<manifest identifier="m1"><organizations/><resources/></manifest>"#);
+        source.insert("course_settings/course_settings.xml", r#"
+            <course identifier="c1" xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <time_zone>America/Denver</time_zone>
+            </course>
+        "#);
+        source.insert("i1/assignment_settings.xml", "<assignment><due_at>2016-03-19T05:59:59</due_at></assignment>");
+        let shift = DateShift::by_days(7).substitute(5, 1);
+        let mut archive = ZipArchive::new(shift.shift_cartridge(&mut source, Cursor::new(Vec::new())).unwrap()).unwrap();
+        let mut contents = String::new();
+        archive.by_name("i1/assignment_settings.xml").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "<assignment><due_at>2016-03-22T05:59:59</due_at></assignment>");
+    }
+
+    #[test]
+    fn rewrites_dates_with_attributes_and_zones() {
+        let shift = DateShift::by_days(1);
+        assert_eq!(shift.shift_xml(r#"<due_at type="datetime">2016-03-20T06:59:59Z</due_at>"#),
+                   r#"<due_at type="datetime">2016-03-21T06:59:59Z</due_at>"#);
+        assert_eq!(shift.shift_xml("<start_at>2016-03-20T06:59:59.5Z</start_at><todo_date>2016-03-20</todo_date>"),
+                   "<start_at>2016-03-21T06:59:59.5Z</start_at><todo_date>2016-03-21</todo_date>");
+        assert_eq!(shift.shift_xml("<due_at_text>2016-03-20</due_at_text>"), "<due_at_text>2016-03-20</due_at_text>");
+    }
+
+    #[test]
+    fn rewrites_xml_and_cartridges() {
+        let xml = "<assignment><title>start_at</title><due_at>2016-03-20T06:59:59</due_at>\
+                   <lock_at></lock_at><created_at>2016-01-01T00:00:00</created_at></assignment>";
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("i1/assignment_settings.xml", CompressionMethod::Deflated).unwrap();
+        writer.write_all(xml.as_bytes()).unwrap();
+        writer.start_file("i1/assignment.html", CompressionMethod::Stored).unwrap();
+        writer.write_all(b"<due_at>2016-03-20T06:59:59</due_at>").unwrap();
+        let mut source = ZipSource::new(writer.finish().unwrap()).unwrap();
+
+        let shifted = DateShift::by_days(1).shift_cartridge(&mut source, Cursor::new(Vec::new())).unwrap();
+        let mut archive = ZipArchive::new(shifted).unwrap();
+        let mut contents = String::new();
+        archive.by_name("i1/assignment_settings.xml").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "<assignment><title>start_at</title><due_at>2016-03-21T06:59:59</due_at>\
+                              <lock_at></lock_at><created_at>2016-01-01T00:00:00</created_at></assignment>");
+        contents.clear();
+        let mut page = archive.by_name("i1/assignment.html").unwrap();
+        assert!(page.compression() == CompressionMethod::Stored);
+        page.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "<due_at>2016-03-20T06:59:59</due_at>");
+    }
+}
@@ -0,0 +1,36 @@
+pub mod date_shift;
+
+pub use self::date_shift::DateShift;
+
+use source::CartridgeSource;
+use std::io::{Read, Result, Seek, Write};
+use zip::{CompressionMethod, ZipWriter};
+
+/// Copies every entry of `source` into a new zipped cartridge written to
+/// `writer`. `transform` sees each entry's name and contents and returns
+/// replacement contents, or `None` to copy the entry unchanged. Entries the
+/// source stored uncompressed are stored again; the rest are deflated.
+/// Folder entries are left out, as the zip writer only writes files.
+pub fn rewrite_cartridge<S, W, F>(source: &mut S, writer: W, mut transform: F) -> Result<W>
+    where S: CartridgeSource,
+          W: Write + Seek,
+          F: FnMut(&str, &[u8]) -> Option<Vec<u8>>
+{
+    let mut zip = ZipWriter::new(writer);
+    for name in source.entries()?.into_iter().filter(|name| !name.ends_with('/')) {
+        let metadata = source.metadata(&name)?;
+        let mut contents = Vec::new();
+        source.open(&name)?.read_to_end(&mut contents)?;
+        let method = if metadata.stored {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        };
+        zip.start_file(name.as_str(), method)?;
+        match transform(&name, &contents) {
+            Some(replacement) => zip.write_all(&replacement)?,
+            None => zip.write_all(&contents)?,
+        }
+    }
+    Ok(zip.finish()?)
+}
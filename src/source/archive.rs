@@ -0,0 +1,37 @@
+use super::{CartridgeSource, EntryMetadata};
+use std::io::{Read, Result, Seek};
+use zip::{CompressionMethod, ZipArchive};
+
+/// A zipped cartridge, as exported.
+pub struct ZipSource<R: Read + Seek> {
+    pub archive: ZipArchive<R>,
+}
+
+impl<R: Read + Seek> ZipSource<R> {
+    pub fn new(reader: R) -> Result<ZipSource<R>> {
+        Ok(ZipSource { archive: ZipArchive::new(reader)? })
+    }
+}
+
+impl<R: Read + Seek> CartridgeSource for ZipSource<R> {
+    fn entries(&mut self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        for index in 0..self.archive.len() {
+            names.push(self.archive.by_index(index)?.name().to_string());
+        }
+        Ok(names)
+    }
+
+    fn open<'a>(&'a mut self, name: &str) -> Result<Box<dyn Read + 'a>> {
+        Ok(Box::new(self.archive.by_name(name)?))
+    }
+
+    fn metadata(&mut self, name: &str) -> Result<EntryMetadata> {
+        let file = self.archive.by_name(name)?;
+        Ok(EntryMetadata {
+            size: file.size(),
+            compressed_size: file.compressed_size(),
+            stored: file.compression() == CompressionMethod::Stored,
+        })
+    }
+}
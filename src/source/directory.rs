@@ -0,0 +1,57 @@
+use super::{CartridgeSource, EntryMetadata};
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Read, Result};
+use std::path::{Path, PathBuf};
+
+/// A cartridge extracted to a directory, like an author's working copy.
+pub struct DirectorySource {
+    pub root: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new<P: AsRef<Path>>(root: P) -> DirectorySource {
+        DirectorySource { root: root.as_ref().to_path_buf() }
+    }
+
+    /// The file an entry name refers to. Names that would leave the
+    /// directory aren't entries.
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        if name.starts_with('/') || name.split(['/', '\\']).any(|segment| segment == "..") {
+            return Err(Error::new(ErrorKind::NotFound, format!("no entry named {}", name)));
+        }
+        Ok(self.root.join(name))
+    }
+}
+
+impl CartridgeSource for DirectorySource {
+    fn entries(&mut self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        collect_files(&self.root, "", &mut names)?;
+        names.sort();
+        Ok(names)
+    }
+
+    fn open<'a>(&'a mut self, name: &str) -> Result<Box<dyn Read + 'a>> {
+        Ok(Box::new(File::open(self.path(name)?)?))
+    }
+
+    fn metadata(&mut self, name: &str) -> Result<EntryMetadata> {
+        let size = fs::metadata(self.path(name)?)?.len();
+        Ok(EntryMetadata { size, compressed_size: size, stored: false })
+    }
+}
+
+/// Adds the files under `folder` to `names` as entry names, which are
+/// joined with `/` whatever the platform.
+fn collect_files(folder: &Path, prefix: &str, names: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &format!("{}/", name), names)?;
+        } else {
+            names.push(name);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use super::{CartridgeSource, EntryMetadata};
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Read, Result};
+
+/// A cartridge whose entries are held in memory, such as one built in a
+/// test.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemorySource {
+    pub entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemorySource {
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    pub fn insert<N: Into<String>, C: Into<Vec<u8>>>(&mut self, name: N, contents: C) {
+        self.entries.insert(name.into(), contents.into());
+    }
+
+    fn get(&self, name: &str) -> Result<&Vec<u8>> {
+        self.entries
+            .get(name)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("no entry named {}", name)))
+    }
+}
+
+impl CartridgeSource for MemorySource {
+    fn entries(&mut self) -> Result<Vec<String>> {
+        Ok(self.entries.keys().cloned().collect())
+    }
+
+    fn open<'a>(&'a mut self, name: &str) -> Result<Box<dyn Read + 'a>> {
+        Ok(Box::new(self.get(name)?.as_slice()))
+    }
+
+    fn metadata(&mut self, name: &str) -> Result<EntryMetadata> {
+        let size = self.get(name)?.len() as u64;
+        Ok(EntryMetadata { size, compressed_size: size, stored: false })
+    }
+}
@@ -0,0 +1,94 @@
+pub mod archive;
+pub mod directory;
+pub mod memory;
+
+pub use self::archive::ZipSource;
+pub use self::directory::DirectorySource;
+pub use self::memory::MemorySource;
+
+use std::io::{Read, Result};
+
+/// Where a cartridge's files are read from: a zip, an extracted directory,
+/// or entries built in memory. Entry names are paths joined with `/`, as in
+/// a zip.
+pub trait CartridgeSource {
+    /// The name of every entry. Folders, where they're listed, end with `/`.
+    fn entries(&mut self) -> Result<Vec<String>>;
+
+    fn open<'a>(&'a mut self, name: &str) -> Result<Box<dyn Read + 'a>>;
+
+    fn metadata(&mut self, name: &str) -> Result<EntryMetadata>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EntryMetadata {
+    pub size: u64,
+    /// The same as `size` for entries that aren't compressed.
+    pub compressed_size: u64,
+    /// Whether the entry was stored without compression, as zips may.
+    pub stored: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CartridgeSource, MemorySource};
+    use rewrite::DateShift;
+    use std::io::{Cursor, ErrorKind, Read};
+    use zip::{CompressionMethod, ZipArchive};
+    use {process_source, shift_source_dates};
+
+    #[test]
+    fn reads_memory_entries() {
+        let mut source = MemorySource::new();
+        source.insert("web_resources/notes.txt", "Bring a hand lens.");
+        assert_eq!(source.entries().unwrap(), vec!["web_resources/notes.txt"]);
+        let mut contents = String::new();
+        source.open("web_resources/notes.txt").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "Bring a hand lens.");
+        assert_eq!(source.metadata("web_resources/notes.txt").unwrap().size, 18);
+        assert_eq!(source.open("missing.txt").err().unwrap().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn summarizes_memory_sources() {
+        let mut source = MemorySource::new();
+        source.insert("imsmanifest.xml", r#"
+            <manifest identifier="m1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+              <organizations>
+                <organization identifier="org_1">
+                  <item identifier="root">
+                    <item identifier="week_1">
+                      <title>Week 1</title>
+                      <item identifier="i1" identifierref="r1"><title>Notes</title></item>
+                    </item>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource identifier="r1" type="webcontent" href="web_resources/notes.txt"/>
+              </resources>
+            </manifest>
+        "#);
+        source.insert("web_resources/notes.txt", "Bring a hand lens.");
+        let summary = process_source(&mut source).unwrap();
+        assert_eq!(summary.modules[0].items[0].title, "Notes");
+        let notes = summary.files.file("web_resources/notes.txt").unwrap();
+        assert_eq!((notes.size, notes.compressed_size), (18, 18));
+        assert_eq!(notes.identifier.as_deref(), Some("r1"));
+    }
+
+    #[test]
+    fn shifts_dates_of_memory_sources() {
+        let mut source = MemorySource::new();
+        source.insert("course_settings/events.xml", "<event><start_at>2016-03-20T18:00:00</start_at></event>");
+        source.insert("web_resources/", "");
+        let shifted = shift_source_dates(&mut source, Cursor::new(Vec::new()), &DateShift::by_days(2)).unwrap();
+        let mut archive = ZipArchive::new(shifted).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut events = archive.by_name("course_settings/events.xml").unwrap();
+        assert!(events.compression() == CompressionMethod::Deflated);
+        let mut contents = String::new();
+        events.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "<event><start_at>2016-03-22T18:00:00</start_at></event>");
+    }
+}
@@ -0,0 +1,73 @@
+use common::{AssignmentGroup, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+pub struct AssignmentGroupsHandler {
+    pub groups: Vec<AssignmentGroup>,
+    pub stack: Vec<Node>,
+    drop_type: Option<String>,
+}
+
+impl AssignmentGroupsHandler {
+    pub fn new() -> AssignmentGroupsHandler {
+        AssignmentGroupsHandler {
+            groups: Vec::new(),
+            stack: Vec::new(),
+            drop_type: None,
+        }
+    }
+
+    pub fn finalize(self) -> Vec<AssignmentGroup> {
+        self.groups
+    }
+}
+
+impl ParseHandler for AssignmentGroupsHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        match Node::path(&self.stack).as_slice() {
+            ["assignmentGroups", "assignmentGroup"] => {
+                self.groups.push(AssignmentGroup::new(node.find("identifier")))
+            }
+            ["assignmentGroups", "assignmentGroup", "rules", "rule"] => self.drop_type = None,
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    /// Rules look like `<rule><drop_type>drop_lowest</drop_type><drop_count>1</drop_count></rule>`
+    /// or `<rule><drop_type>never_drop</drop_type><identifierref>…</identifierref></rule>`.
+    fn receive_chars(&mut self, chars: String) {
+        let value = chars.trim().to_string();
+        let path = Node::path(&self.stack);
+        let group = match self.groups.last_mut() {
+            Some(group) => group,
+            None => return,
+        };
+        match path.as_slice() {
+            ["assignmentGroups", "assignmentGroup", "title"] => group.title = value,
+            ["assignmentGroups", "assignmentGroup", "position"] => group.position = value.parse().ok(),
+            ["assignmentGroups", "assignmentGroup", "group_weight"] => {
+                group.group_weight = value.parse().unwrap_or(0.0)
+            }
+            ["assignmentGroups", "assignmentGroup", "rules", "rule", "drop_type"] => self.drop_type = Some(value),
+            ["assignmentGroups", "assignmentGroup", "rules", "rule", "drop_count"] => {
+                let count = value.parse().unwrap_or(0);
+                match self.drop_type.as_deref() {
+                    Some("drop_lowest") => group.drop_lowest = count,
+                    Some("drop_highest") => group.drop_highest = count,
+                    _ => {}
+                }
+            }
+            ["assignmentGroups", "assignmentGroup", "rules", "rule", "identifierref"]
+                if self.drop_type.as_deref() == Some("never_drop") =>
+            {
+                group.never_drop.push(value)
+            }
+            _ => {}
+        }
+    }
+}
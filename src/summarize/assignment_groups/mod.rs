@@ -0,0 +1,68 @@
+mod handler;
+
+use common::AssignmentGroup;
+use std::io::Read;
+use summarize::assignment_groups::handler::AssignmentGroupsHandler;
+use summarize::utils::handle_parse;
+
+pub const ASSIGNMENT_GROUPS_PATH: &str = "course_settings/assignment_groups.xml";
+
+pub fn parse<R: Read>(assignment_groups: R) -> Vec<AssignmentGroup> {
+    let mut handler = AssignmentGroupsHandler::new();
+    handle_parse(assignment_groups, &mut handler);
+    handler.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_groups_and_rules() {
+        let groups = parse(xml_string().as_bytes());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].identifier, "i446792212cfb602a9376d7469e5ae144");
+        assert_eq!(groups[0].title, "Assignments");
+        assert_eq!(groups[0].position, Some(1));
+        assert_eq!(groups[0].group_weight, 60.0);
+        assert_eq!(groups[0].drop_lowest, 1);
+        assert_eq!(groups[0].drop_highest, 0);
+        assert_eq!(groups[0].never_drop, vec!["id8f3d6a79d9485366fd70b9ced83e729"]);
+        assert_eq!(groups[1].group_weight, 40.0);
+        assert_eq!(groups[1].drop_highest, 2);
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <assignmentGroups xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+          <assignmentGroup identifier="i446792212cfb602a9376d7469e5ae144">
+            <title>Assignments</title>
+            <position>1</position>
+            <group_weight>60</group_weight>
+            <rules>
+              <rule>
+                <drop_type>drop_lowest</drop_type>
+                <drop_count>1</drop_count>
+              </rule>
+              <rule>
+                <drop_type>never_drop</drop_type>
+                <identifierref>id8f3d6a79d9485366fd70b9ced83e729</identifierref>
+              </rule>
+            </rules>
+          </assignmentGroup>
+          <assignmentGroup identifier="ic86857640947ccf2e9975af84b76c3ab">
+            <title>Quizzes</title>
+            <position>2</position>
+            <group_weight>40.0</group_weight>
+            <rules>
+              <rule>
+                <drop_type>drop_highest</drop_type>
+                <drop_count>2</drop_count>
+              </rule>
+            </rules>
+          </assignmentGroup>
+        </assignmentGroups>
+      "#
+    }
+}
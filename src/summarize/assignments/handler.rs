@@ -0,0 +1,81 @@
+use common::{Assignment, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Roots whose `<assignment>` child holds settings: `assessment_meta.xml`
+/// and `topicMeta` files.
+const SETTINGS_PARENTS: [&str; 2] = ["quiz", "topicMeta"];
+
+/// Finds the `<assignment>` element of an assignment, quiz or discussion
+/// settings file. It is the root of `assignment_settings.xml` and a child of
+/// the root in `assessment_meta.xml` and `topicMeta` files.
+pub struct AssignmentHandler {
+    pub assignment: Option<Assignment>,
+    pub stack: Vec<Node>,
+    assignment_depth: usize,
+    topic_id: Option<String>,
+}
+
+impl AssignmentHandler {
+    pub fn new() -> AssignmentHandler {
+        AssignmentHandler {
+            assignment: None,
+            stack: Vec::new(),
+            assignment_depth: 0,
+            topic_id: None,
+        }
+    }
+
+    pub fn finalize(self) -> Option<Assignment> {
+        let topic_id = self.topic_id;
+        self.assignment.map(|mut assignment| {
+            if let Some(topic_id) = topic_id {
+                assignment.resource_identifier = topic_id;
+            }
+            assignment
+        })
+    }
+}
+
+impl ParseHandler for AssignmentHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        let depth = self.stack.len();
+        if !node.has_name("assignment") || self.assignment.is_some() || depth > 2 {
+            return;
+        }
+        if depth == 2 && !SETTINGS_PARENTS.iter().any(|parent| self.stack[0].has_name(parent)) {
+            return;
+        }
+        let mut assignment = Assignment::new(node.find("identifier"));
+        assignment.resource_identifier = if depth == 1 {
+            assignment.identifier.clone()
+        } else {
+            self.stack[0].find("identifier").unwrap_or_default()
+        };
+        self.assignment = Some(assignment);
+        self.assignment_depth = depth;
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let depth = self.stack.len();
+        if depth == 2 && self.stack[0].has_name("topicMeta") && self.stack[1].has_name("topic_id") {
+            self.topic_id = Some(chars.trim().to_string());
+            return;
+        }
+        let within_assignment = self.assignment.is_some()
+            && depth == self.assignment_depth + 1
+            && self.stack[self.assignment_depth - 1].has_name("assignment");
+        if !within_assignment {
+            return;
+        }
+        let key = self.stack[depth - 1].name_str().to_string();
+        if let Some(ref mut assignment) = self.assignment {
+            assignment.settings.entry(key).or_default().push_str(&chars);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+mod handler;
+
+use common::Assignment;
+use std::io::Read;
+use summarize::assignments::handler::AssignmentHandler;
+use summarize::utils::{handle_parse, parse_flag};
+
+/// Whether an archive entry can hold assignment settings. Graded discussions
+/// keep theirs in a `topicMeta` file at the top of the archive, under no
+/// fixed name, so other top-level XML files are candidates too; `parse`
+/// only takes an `<assignment>` that is the root, or a child of a `<quiz>`
+/// or `<topicMeta>` root.
+pub fn is_settings_path(path: &str) -> bool {
+    path.ends_with("/assignment_settings.xml")
+        || path.ends_with("/assessment_meta.xml")
+        || (path.ends_with(".xml") && !path.contains('/') && path != "imsmanifest.xml")
+}
+
+pub fn parse<R: Read>(settings: R) -> Option<Assignment> {
+    let mut handler = AssignmentHandler::new();
+    handle_parse(settings, &mut handler);
+    handler.finalize().map(fill_fields)
+}
+
+fn fill_fields(mut assignment: Assignment) -> Assignment {
+    {
+        let settings = &assignment.settings;
+        let text = |key: &str| settings.get(key).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        assignment.title = text("title").unwrap_or_default();
+        assignment.assignment_group_identifier_ref = text("assignment_group_identifierref");
+        assignment.points_possible = text("points_possible").and_then(|points| points.parse().ok());
+        assignment.grading_type = text("grading_type");
+        assignment.grading_standard_identifier_ref = text("grading_standard_identifierref");
+        assignment.rubric_identifier_ref = text("rubric_identifierref");
+        assignment.quiz_identifier_ref = text("quiz_identifierref");
+        assignment.rubric_use_for_grading = text("rubric_use_for_grading").is_some_and(|flag| parse_flag(&flag));
+        assignment.workflow_state = text("workflow_state");
+        assignment.position = text("position").and_then(|position| position.parse().ok());
+        assignment.submission_types = text("submission_types")
+            .map(|types| types.split(',').map(|t| t.trim().to_string()).collect())
+            .unwrap_or_default();
+        assignment.due_at = text("due_at");
+        assignment.unlock_at = text("unlock_at");
+        assignment.lock_at = text("lock_at");
+        assignment.omit_from_final_grade = text("omit_from_final_grade").is_some_and(|omit| parse_flag(&omit));
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_assignment_settings() {
+        let assignment = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <assignment identifier="id8f3d6a79d9485366fd70b9ced83e729" xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <title>Assignment 7</title>
+              <due_at>2016-03-20T06:59:59</due_at>
+              <assignment_group_identifierref>iaca5606189b68f467c6ae28d110cd358</assignment_group_identifierref>
+              <workflow_state>published</workflow_state>
+              <points_possible>100</points_possible>
+              <grading_type>letter_grade</grading_type>
+              <grading_standard_identifierref>i7de25791471f7666845d173c58e99353</grading_standard_identifierref>
+              <submission_types>online_text_entry,online_upload</submission_types>
+              <position>13</position>
+              <rubric_identifierref>ibff36139d637040dc9402090a3ec75f8</rubric_identifierref>
+              <rubric_use_for_grading>true</rubric_use_for_grading>
+            </assignment>
+        "#.as_bytes()).unwrap();
+        assert_eq!(assignment.identifier, "id8f3d6a79d9485366fd70b9ced83e729");
+        assert_eq!(assignment.resource_identifier, "id8f3d6a79d9485366fd70b9ced83e729");
+        assert_eq!(assignment.title, "Assignment 7");
+        assert_eq!(assignment.assignment_group_identifier_ref, Some("iaca5606189b68f467c6ae28d110cd358".to_string()));
+        assert_eq!(assignment.points_possible, Some(100.0));
+        assert_eq!(assignment.submission_types, vec!["online_text_entry", "online_upload"]);
+        assert_eq!(assignment.due_at, Some("2016-03-20T06:59:59".to_string()));
+        assert_eq!(assignment.grading_standard_identifier_ref, Some("i7de25791471f7666845d173c58e99353".to_string()));
+        assert!(assignment.uses_grading_scheme());
+        assert_eq!(assignment.rubric_identifier_ref, Some("ibff36139d637040dc9402090a3ec75f8".to_string()));
+        assert!(assignment.rubric_use_for_grading);
+        assert!(assignment.counts_toward_grade());
+    }
+
+    #[test]
+    fn parses_quiz_assignment() {
+        let assignment = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <quiz identifier="i9b472fe6bfd6a729f056be5da05da798" xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <title>Quiz 86</title>
+              <points_possible>0</points_possible>
+              <assignment identifier="if3529d57e6c0e662dcac7f283e38361f">
+                <title>Quiz 86</title>
+                <assignment_group_identifierref>iaca5606189b68f467c6ae28d110cd358</assignment_group_identifierref>
+                <workflow_state>unpublished</workflow_state>
+                <quiz_identifierref>i9b472fe6bfd6a729f056be5da05da798</quiz_identifierref>
+                <points_possible>10</points_possible>
+              </assignment>
+            </quiz>
+        "#.as_bytes()).unwrap();
+        assert_eq!(assignment.identifier, "if3529d57e6c0e662dcac7f283e38361f");
+        assert_eq!(assignment.resource_identifier, "i9b472fe6bfd6a729f056be5da05da798");
+        assert_eq!(assignment.points_possible, Some(10.0));
+        assert_eq!(assignment.quiz_identifier_ref, Some("i9b472fe6bfd6a729f056be5da05da798".to_string()));
+        assert!(!assignment.counts_toward_grade());
+    }
+
+    #[test]
+    fn parses_graded_discussion() {
+        let assignment = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <topicMeta identifier="i12a9635e81cf45d6cbc1f8eb0ee3d891" xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <topic_id>i2e9c3d7b0a8e5f9c0a1b2c3d4e5f6a7b</topic_id>
+              <title>Graded Discussion</title>
+              <assignment identifier="i0a4c1b0d7f1a5a2e9b6c3d8e7f2a1b0c">
+                <title>Graded Discussion</title>
+                <points_possible>5</points_possible>
+              </assignment>
+            </topicMeta>
+        "#.as_bytes()).unwrap();
+        assert_eq!(assignment.resource_identifier, "i2e9c3d7b0a8e5f9c0a1b2c3d4e5f6a7b");
+        assert_eq!(assignment.points_possible, Some(5.0));
+    }
+
+    #[test]
+    fn ignores_other_top_level_files() {
+        assert!(parse(r#"
+            <gradebook xmlns="http://vendor.example.com/export">
+              <assignment identifier="g1"><title>Column 1</title></assignment>
+            </gradebook>
+        "#.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn ignores_ungraded_topics() {
+        assert!(parse(r#"<topicMeta identifier="i01"><title>Announcement</title></topicMeta>"#.as_bytes()).is_none());
+    }
+}
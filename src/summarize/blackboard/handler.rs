@@ -0,0 +1,43 @@
+use common::ParseHandler;
+use summarize::blackboard::BlackboardContent;
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Reads the fields of a Blackboard `<CONTENT>` record that decide how its
+/// item is shown.
+pub struct BlackboardContentHandler {
+    pub content: BlackboardContent,
+    pub stack: Vec<Node>,
+}
+
+impl BlackboardContentHandler {
+    pub fn new() -> BlackboardContentHandler {
+        BlackboardContentHandler {
+            content: BlackboardContent::default(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> BlackboardContent {
+        self.content
+    }
+}
+
+impl ParseHandler for BlackboardContentHandler {
+    fn enter(&mut self, node: Node) {
+        let value = node.find("value").map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        match (self.stack.len(), node.name_str()) {
+            (1, "TITLE") => self.content.title = value,
+            (1, "CONTENTHANDLER") => self.content.handler = value,
+            (1, "URL") => self.content.url = value,
+            _ => {}
+        }
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, _chars: String) {}
+}
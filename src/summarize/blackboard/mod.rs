@@ -0,0 +1,103 @@
+mod handler;
+
+use common::{ItemType, Manifest};
+use std::collections::HashMap;
+use std::io::Read;
+use summarize::blackboard::handler::BlackboardContentHandler;
+use summarize::utils::{handle_parse, typestr_to_type};
+
+/// A Blackboard content record, from the `.dat` file a resource names in
+/// its `bb:file` attribute.
+#[derive(Debug, Default, PartialEq)]
+pub struct BlackboardContent {
+    pub title: Option<String>,
+    /// The content handler, like `resource/x-bb-externallink`.
+    pub handler: Option<String>,
+    pub url: Option<String>,
+}
+
+pub fn parse<R: Read>(content: R) -> BlackboardContent {
+    let mut handler = BlackboardContentHandler::new();
+    handle_parse(content, &mut handler);
+    handler.finalize()
+}
+
+/// Blackboard exports most content as `webcontent`, so the content handler
+/// decides what a resource really is: a link, a folder, a test. Items take
+/// the resource's corrected type, the link's URL, and the record's title
+/// when they have none.
+pub fn apply(contents: &HashMap<String, BlackboardContent>, manifest: &mut Manifest) {
+    for resource in manifest.resources.iter_mut() {
+        let handler = contents.get(&resource.identifier).and_then(|content| content.handler.as_ref());
+        match handler.map(|handler| typestr_to_type(handler)) {
+            Some(ItemType::Unknown { .. }) | None => {}
+            Some(item_type) => resource.item_type = item_type,
+        }
+    }
+    for item in manifest.modules.iter_mut().flat_map(|module| module.items.iter_mut()) {
+        let content = match contents.get(&item.identifier_ref) {
+            Some(content) => content,
+            None => continue,
+        };
+        if let Some(resource) = manifest.resources.iter().find(|resource| resource.identifier == item.identifier_ref) {
+            item.item_type = resource.item_type.clone();
+        }
+        if item.url.is_none() && item.item_type == ItemType::ExternalUrl {
+            item.url = content.url.clone();
+        }
+        if item.title.trim().is_empty() {
+            item.title = content.title.clone().unwrap_or_default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, parse};
+    use common::ItemType;
+    use source::{CartridgeSource, ZipSource};
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::Read;
+    use summarize::manifest;
+
+    #[test]
+    fn parses_content_records() {
+        let content = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <CONTENT id="_1234_1">
+              <TITLE value="Lecture notes"/>
+              <BODY><TEXT>&lt;p&gt;Slides for unit 1&lt;/p&gt;</TEXT><TYPE value="H"/></BODY>
+              <CONTENTHANDLER value="resource/x-bb-document"/>
+              <URL value=""/>
+            </CONTENT>
+        "#.as_bytes());
+        assert_eq!(content.title, Some("Lecture notes".to_string()));
+        assert_eq!(content.handler, Some("resource/x-bb-document".to_string()));
+        assert_eq!(content.url, None);
+    }
+
+    #[test]
+    fn applies_content_records() {
+        let mut source = ZipSource::new(File::open("fixtures/blackboard.imscc").unwrap()).unwrap();
+        let mut xml = Vec::new();
+        source.open("imsmanifest.xml").unwrap().read_to_end(&mut xml).unwrap();
+        let mut manifest = manifest::parse(&xml);
+        let mut contents = HashMap::new();
+        for resource in &manifest.resources {
+            if let Some(ref dat) = resource.extension_file {
+                contents.insert(resource.identifier.clone(), parse(source.open(dat).unwrap()));
+            }
+        }
+        assert_eq!(manifest.find_resource("res00007").unwrap().item_type, ItemType::WebContent);
+        apply(&contents, &mut manifest);
+
+        assert_eq!(manifest.find_resource("res00004").unwrap().item_type, ItemType::SubHeader);
+        assert_eq!(manifest.find_resource("res00006").unwrap().item_type, ItemType::SubHeader);
+        let items: Vec<_> = manifest.modules.iter().flat_map(|module| module.items.iter()).collect();
+        let video = items.iter().find(|item| item.identifier == "itm00007").unwrap();
+        assert_eq!(video.title, "Safety video");
+        assert_eq!(video.item_type, ItemType::ExternalUrl);
+        assert_eq!(video.url.as_deref(), Some("https://videos.example.edu/lab-safety"));
+    }
+}
@@ -0,0 +1,44 @@
+use common::{CourseSettings, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Collects the text of every child of `<course>`; `finalize` picks out the
+/// settings that get their own fields.
+pub struct CourseSettingsHandler {
+    pub settings: CourseSettings,
+    pub stack: Vec<Node>,
+}
+
+impl CourseSettingsHandler {
+    pub fn new() -> CourseSettingsHandler {
+        CourseSettingsHandler {
+            settings: CourseSettings::default(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> CourseSettings {
+        self.settings
+    }
+}
+
+impl ParseHandler for CourseSettingsHandler {
+    fn enter(&mut self, node: Node) {
+        if self.stack.is_empty() && node.has_name("course") {
+            self.settings = CourseSettings::new(node.find("identifier"));
+        }
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        if self.stack.len() != 2 || !self.stack[0].has_name("course") {
+            return;
+        }
+        let key = self.stack[1].name_str().to_string();
+        self.settings.settings.entry(key).or_default().push_str(&chars);
+    }
+}
@@ -0,0 +1,117 @@
+mod handler;
+
+use common::{CourseSettings, CourseTab};
+use std::io::Read;
+use summarize::course_settings::handler::CourseSettingsHandler;
+use summarize::json::Json;
+use summarize::utils::{handle_parse, parse_flag};
+
+pub const COURSE_SETTINGS_PATH: &str = "course_settings/course_settings.xml";
+
+pub fn parse<R: Read>(course_settings: R) -> CourseSettings {
+    let mut handler = CourseSettingsHandler::new();
+    handle_parse(course_settings, &mut handler);
+    let mut settings = handler.finalize();
+    fill_fields(&mut settings);
+    settings
+}
+
+fn fill_fields(course: &mut CourseSettings) {
+    let text = |key: &str| course.settings.get(key).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    let flag = |key: &str| course.settings.get(key).is_some_and(|value| parse_flag(value));
+    *course = CourseSettings {
+        title: text("title").unwrap_or_default(),
+        course_code: text("course_code"),
+        start_at: text("start_at"),
+        conclude_at: text("conclude_at"),
+        default_view: text("default_view"),
+        time_zone: text("time_zone"),
+        locale: text("locale"),
+        license: text("license"),
+        grading_standard_enabled: flag("grading_standard_enabled"),
+        grading_standard_identifier_ref: text("grading_standard_identifier_ref"),
+        group_weighting_scheme: text("group_weighting_scheme"),
+        is_public: flag("is_public"),
+        is_public_to_auth_users: flag("is_public_to_auth_users"),
+        public_syllabus: flag("public_syllabus"),
+        indexed: flag("indexed"),
+        self_enrollment: flag("self_enrollment"),
+        open_enrollment: flag("open_enrollment"),
+        restrict_enrollments_to_course_dates: flag("restrict_enrollments_to_course_dates"),
+        tabs: text("tab_configuration").map_or(Vec::new(), |tabs| parse_tabs(&tabs)),
+        identifier: course.identifier.clone(),
+        settings: course.settings.clone(),
+    };
+}
+
+/// `tab_configuration` is a JSON array like `[{"id":0},{"id":3,"hidden":true}]`.
+fn parse_tabs(json: &str) -> Vec<CourseTab> {
+    let json = match Json::parse(json) {
+        Some(json) => json,
+        None => return Vec::new(),
+    };
+    json.as_array()
+        .map(|tabs| {
+            tabs.iter()
+                .filter_map(|tab| {
+                    let id = tab.get("id").and_then(|id| id.to_key())?;
+                    let hidden = tab.get("hidden").and_then(|hidden| hidden.as_bool()).unwrap_or(false);
+                    Some(CourseTab { id, hidden })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_course_settings() {
+        let settings = parse(xml_string().as_bytes());
+        assert_eq!(settings.identifier, "i5e0d8279664539be677db96c71643966");
+        assert_eq!(settings.title, "Tommy's Awesome Course");
+        assert_eq!(settings.course_code, Some("666-13".to_string()));
+        assert_eq!(settings.start_at, Some("2016-01-11T07:00:00".to_string()));
+        assert_eq!(settings.conclude_at, Some("2016-05-06T06:00:00".to_string()));
+        assert_eq!(settings.default_view, Some("feed".to_string()));
+        assert_eq!(settings.time_zone, Some("America/Denver".to_string()));
+        assert_eq!(settings.license, Some("private".to_string()));
+        assert!(settings.grading_standard_enabled);
+        assert_eq!(settings.grading_standard_identifier_ref, Some("i7de25791471f7666845d173c58e99353".to_string()));
+        assert!(settings.self_enrollment);
+        assert!(!settings.is_public);
+        assert_eq!(settings.visibility(), "institution");
+        assert_eq!(settings.settings.get("storage_quota"), Some(&"52428800000".to_string()));
+    }
+
+    #[test]
+    fn parses_navigation_tabs() {
+        let settings = parse(xml_string().as_bytes());
+        assert_eq!(settings.tabs.len(), 4);
+        assert_eq!(settings.hidden_tabs(), vec!["3", "context_external_tool_5"]);
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <course identifier="i5e0d8279664539be677db96c71643966" xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+          <title>Tommy's Awesome Course</title>
+          <course_code>666-13</course_code>
+          <start_at>2016-01-11T07:00:00</start_at>
+          <conclude_at>2016-05-06T06:00:00</conclude_at>
+          <tab_configuration>[{"id":0},{"id":3,"hidden":true},{"id":"context_external_tool_5","hidden":true},{"id":1}]</tab_configuration>
+          <is_public>false</is_public>
+          <is_public_to_auth_users>true</is_public_to_auth_users>
+          <default_view>feed</default_view>
+          <license>private</license>
+          <time_zone>America/Denver</time_zone>
+          <grading_standard_enabled>true</grading_standard_enabled>
+          <storage_quota>52428800000</storage_quota>
+          <grading_standard_identifier_ref>i7de25791471f7666845d173c58e99353</grading_standard_identifier_ref>
+          <self_enrollment>true</self_enrollment>
+        </course>
+      "#
+    }
+}
@@ -0,0 +1,45 @@
+use common::{CalendarEvent, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Collects the text of every child of each `<event>`.
+pub struct EventsHandler {
+    pub events: Vec<CalendarEvent>,
+    pub stack: Vec<Node>,
+}
+
+impl EventsHandler {
+    pub fn new() -> EventsHandler {
+        EventsHandler {
+            events: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<CalendarEvent> {
+        self.events
+    }
+}
+
+impl ParseHandler for EventsHandler {
+    fn enter(&mut self, node: Node) {
+        if self.stack.len() == 1 && node.has_name("event") {
+            self.events.push(CalendarEvent::new(node.find("identifier")));
+        }
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        if self.stack.len() != 3 || !self.stack[1].has_name("event") {
+            return;
+        }
+        let key = self.stack[2].name_str().to_string();
+        if let Some(event) = self.events.last_mut() {
+            event.settings.entry(key).or_default().push_str(&chars);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+mod handler;
+
+use common::CalendarEvent;
+use std::io::Read;
+use summarize::events::handler::EventsHandler;
+use summarize::utils::{handle_parse, parse_flag};
+
+pub const EVENTS_PATH: &str = "course_settings/events.xml";
+
+pub fn parse<R: Read>(events: R) -> Vec<CalendarEvent> {
+    let mut handler = EventsHandler::new();
+    handle_parse(events, &mut handler);
+    handler.finalize().into_iter().map(fill_fields).collect()
+}
+
+fn fill_fields(mut event: CalendarEvent) -> CalendarEvent {
+    {
+        let settings = &event.settings;
+        let text = |key: &str| settings.get(key).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        event.title = text("title").unwrap_or_default();
+        event.description = text("description");
+        event.start_at = text("start_at");
+        event.end_at = text("end_at");
+        event.location_name = text("location_name");
+        event.location_address = text("location_address");
+        event.all_day = text("all_day").is_some_and(|all_day| parse_flag(&all_day));
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_events() {
+        let events = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <events xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <event identifier="ib246a8580f75b1e0415d14b3ad907bf7">
+                <title>Example Event</title>
+                <description></description>
+                <start_at>2014-04-22T18:00:00</start_at>
+                <end_at>2014-04-22T19:00:00</end_at>
+              </event>
+              <event identifier="i5f1d3c0b9a8e7f6d5c4b3a2f1e0d9c8b">
+                <title>Review Day</title>
+                <description>&lt;p&gt;Bring questions&lt;/p&gt;</description>
+                <start_at>2014-04-25T00:00:00</start_at>
+                <location_name>Room 101</location_name>
+                <all_day>true</all_day>
+              </event>
+            </events>
+        "#.as_bytes());
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].identifier, "ib246a8580f75b1e0415d14b3ad907bf7");
+        assert_eq!(events[0].title, "Example Event");
+        assert_eq!(events[0].description, None);
+        assert_eq!(events[0].end_at, Some("2014-04-22T19:00:00".to_string()));
+        assert_eq!(events[1].description, Some("<p>Bring questions</p>".to_string()));
+        assert_eq!(events[1].location_name, Some("Room 101".to_string()));
+        assert!(events[1].all_day);
+    }
+}
@@ -0,0 +1,88 @@
+use common::Exporter;
+use regex::Regex;
+
+pub const CANVAS_EXPORT_PATH: &str = "course_settings/canvas_export.txt";
+
+/// Recognizes the exporter from the archive's entries, the namespace URIs
+/// the manifest declares, and the `FN` of the vCards in its metadata, where
+/// exporters sign as publisher or author. Titles and descriptions are not
+/// looked at, since a course may well mention another system.
+pub fn detect(manifest: &str, paths: &[String]) -> Exporter {
+    let signature = signature(manifest);
+    let signed = |name: &str| signature.iter().any(|value| value.contains(name));
+    let has_path = |prefix: &str| paths.iter().any(|path| path.starts_with(prefix));
+    if has_path(CANVAS_EXPORT_PATH) || signed("canvas.instructure.com") {
+        Exporter::Canvas
+    } else if has_path("csfiles/") || signed("blackboard") {
+        Exporter::Blackboard
+    } else if signed("desire2learn.com") || signed("brightspace") || signed("d2l") {
+        Exporter::D2L
+    } else if signed("schoology") {
+        Exporter::Schoology
+    } else if signed("moodle") {
+        Exporter::Moodle
+    } else {
+        Exporter::Unknown
+    }
+}
+
+/// The namespace URIs and vCard `FN`s of the manifest, lowercased.
+fn signature(manifest: &str) -> Vec<String> {
+    lazy_static! {
+        static ref NAMESPACE_R: Regex = Regex::new(r#"xmlns(?::[\w.-]+)?\s*=\s*["']([^"']*)["']"#).unwrap();
+        static ref FORMATTED_NAME_R: Regex = Regex::new(r"(?m)^\s*FN(?:;[^:\r\n]*)?:([^\r\n<]*)").unwrap();
+    }
+    NAMESPACE_R.captures_iter(manifest)
+        .chain(FORMATTED_NAME_R.captures_iter(manifest))
+        .filter_map(|captures| captures.at(1))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect;
+    use common::Exporter;
+
+    fn manifest(metadata: &str, namespaces: &str) -> String {
+        format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+            <manifest identifier="m1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1" {}>
+              <metadata>
+                <schema>IMS Common Cartridge</schema>
+                <schemaversion>1.1.0</schemaversion>
+                {}
+              </metadata>
+              <organizations>
+                <organization identifier="org_1" structure="rooted-hierarchy">
+                  <item identifier="i1"><title>Moodle and Blackboard migration tips</title></item>
+                </organization>
+              </organizations>
+            </manifest>"#, namespaces, metadata)
+    }
+
+    #[test]
+    fn detects_exporters() {
+        let plain = manifest("", "");
+        assert_eq!(detect(&plain, &["course_settings/canvas_export.txt".to_string()]), Exporter::Canvas);
+        assert_eq!(detect(&plain, &["csfiles/home_dir/notes.pdf".to_string()]), Exporter::Blackboard);
+        assert_eq!(detect(&manifest("", r#"xmlns:bb="http://www.blackboard.com/content-packaging/""#), &[]),
+                   Exporter::Blackboard);
+        assert_eq!(detect(&manifest("", r#"xmlns:d2l_2p0="http://desire2learn.com/xsd/d2lcp_v2p0""#), &[]),
+                   Exporter::D2L);
+        let moodle = "<lomimscc:lom><lomimscc:general><lomimscc:title><lomimscc:string>Course</lomimscc:string>\
+                      </lomimscc:title></lomimscc:general><lomimscc:lifeCycle><lomimscc:contribute>\
+                      <lomimscc:entity>BEGIN:VCARD\nFN:Moodle 3.9\nEND:VCARD</lomimscc:entity>\
+                      </lomimscc:contribute></lomimscc:lifeCycle></lomimscc:lom>";
+        assert_eq!(detect(&manifest(moodle, ""), &[]), Exporter::Moodle);
+        assert_eq!(detect(&plain, &["web_resources/a.pdf".to_string()]), Exporter::Unknown);
+    }
+
+    #[test]
+    fn ignores_exporters_named_in_descriptions() {
+        let described = "<lomimscc:lom><lomimscc:general><lomimscc:title><lomimscc:string>Moving from Moodle\
+                         </lomimscc:string></lomimscc:title><lomimscc:description><lomimscc:string>How to bring \
+                         Blackboard Learn and Schoology courses across.</lomimscc:string></lomimscc:description>\
+                         </lomimscc:general></lomimscc:lom>";
+        assert_eq!(detect(&manifest(described, ""), &[]), Exporter::Unknown);
+    }
+}
@@ -0,0 +1,97 @@
+use common::{ParseHandler, UsageRights};
+use std::collections::BTreeMap;
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// The settings of a `<folder>` or `<file>` as exported.
+#[derive(Debug, Default)]
+pub struct ItemMeta {
+    /// A folder's `path` or a file's `identifier`.
+    pub key: String,
+    pub settings: BTreeMap<String, String>,
+    pub usage_rights: Option<UsageRights>,
+}
+
+/// Collects the text of every child of each `<folder>` and `<file>`.
+pub struct FilesMetaHandler {
+    pub folders: Vec<ItemMeta>,
+    pub files: Vec<ItemMeta>,
+    pub stack: Vec<Node>,
+}
+
+impl FilesMetaHandler {
+    pub fn new() -> FilesMetaHandler {
+        FilesMetaHandler {
+            folders: Vec::new(),
+            files: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> (Vec<ItemMeta>, Vec<ItemMeta>) {
+        (self.folders, self.files)
+    }
+
+    fn current(&mut self) -> Option<&mut ItemMeta> {
+        if self.stack.len() < 3 {
+            return None;
+        }
+        match (self.stack[1].name_str(), self.stack[2].name_str()) {
+            ("folders", "folder") => self.folders.last_mut(),
+            ("files", "file") => self.files.last_mut(),
+            _ => None,
+        }
+    }
+}
+
+impl ParseHandler for FilesMetaHandler {
+    fn enter(&mut self, node: Node) {
+        if self.stack.len() == 2 {
+            match (self.stack[1].name_str(), node.name_str()) {
+                ("folders", "folder") => {
+                    self.folders.push(ItemMeta { key: node.find("path").unwrap_or_default(), ..ItemMeta::default() })
+                }
+                ("files", "file") => {
+                    self.files.push(ItemMeta { key: node.find("identifier").unwrap_or_default(), ..ItemMeta::default() })
+                }
+                _ => {}
+            }
+        }
+        let usage_rights = self.stack.len() == 3 && node.has_name("usage_rights");
+        if usage_rights {
+            let use_justification = node.find("use_justification");
+            if let Some(item) = self.current() {
+                item.usage_rights = Some(UsageRights { use_justification, ..UsageRights::default() });
+            }
+        }
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let depth = self.stack.len();
+        if depth != 4 && !(depth == 5 && self.stack[3].has_name("usage_rights")) {
+            return;
+        }
+        let key = self.stack[depth - 1].name_str().to_string();
+        let item = match self.current() {
+            Some(item) => item,
+            None => return,
+        };
+        if depth == 4 {
+            item.settings.entry(key).or_default().push_str(&chars);
+            return;
+        }
+        let value = chars.trim().to_string();
+        if let Some(ref mut rights) = item.usage_rights {
+            match key.as_str() {
+                "legal_copyright" => rights.legal_copyright = Some(value),
+                "license" => rights.license = Some(value),
+                _ => {}
+            }
+        }
+    }
+}
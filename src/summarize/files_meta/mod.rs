@@ -0,0 +1,117 @@
+mod handler;
+
+use common::{FileTree, Resource};
+use std::io::Read;
+use summarize::files_meta::handler::{FilesMetaHandler, ItemMeta};
+use summarize::utils::{handle_parse, parse_flag};
+
+pub const FILES_META_PATH: &str = "course_settings/files_meta.xml";
+
+/// Folder and file state, keyed by folder path and file identifier.
+#[derive(Debug, Default)]
+pub struct FilesMeta {
+    pub folders: Vec<ItemMeta>,
+    pub files: Vec<ItemMeta>,
+}
+
+pub fn parse<R: Read>(files_meta: R) -> FilesMeta {
+    let mut handler = FilesMetaHandler::new();
+    handle_parse(files_meta, &mut handler);
+    let (folders, files) = handler.finalize();
+    FilesMeta { folders, files }
+}
+
+/// Marks files with their resource identifiers, then copies the state
+/// from `files_meta.xml` onto the folders and files it names.
+pub fn apply(meta: &FilesMeta, resources: &[Resource], tree: &mut FileTree) {
+    for resource in resources {
+        if let Some(file) = resource.href.as_ref().and_then(|href| tree.file_mut(href)) {
+            file.identifier = Some(resource.identifier.clone());
+        }
+    }
+    for folder_meta in &meta.folders {
+        // Canvas writes folder paths from the root folder, `course files`.
+        let path = folder_meta.key.trim_start_matches("course files").trim_matches('/');
+        if let Some(folder) = tree.folder_mut(path) {
+            folder.hidden = flag(folder_meta, "hidden");
+            folder.locked = flag(folder_meta, "locked");
+            folder.unlock_at = text(folder_meta, "unlock_at");
+            folder.lock_at = text(folder_meta, "lock_at");
+        }
+    }
+    for file_meta in &meta.files {
+        let href = resources
+            .iter()
+            .find(|resource| resource.identifier == file_meta.key)
+            .and_then(|resource| resource.href.as_ref());
+        if let Some(file) = href.and_then(|href| tree.file_mut(href)) {
+            file.display_name = text(file_meta, "display_name");
+            file.hidden = flag(file_meta, "hidden");
+            file.locked = flag(file_meta, "locked");
+            file.unlock_at = text(file_meta, "unlock_at");
+            file.lock_at = text(file_meta, "lock_at");
+            file.usage_rights = file_meta.usage_rights.clone();
+        }
+    }
+}
+
+fn text(meta: &ItemMeta, key: &str) -> Option<String> {
+    meta.settings.get(key).map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+fn flag(meta: &ItemMeta, key: &str) -> bool {
+    meta.settings.get(key).is_some_and(|value| parse_flag(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, parse};
+    use common::{FileEntry, FileTree, ItemType, Resource};
+
+    #[test]
+    fn applies_files_meta() {
+        let meta = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <fileMeta xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <folders>
+                <folder path="course files/Example Folder/">
+                  <locked>true</locked>
+                  <unlock_at>2016-02-01T07:00:00</unlock_at>
+                </folder>
+              </folders>
+              <files>
+                <file identifier="i97521b4eda791dd993051a519e424e33">
+                  <display_name>Syllabus draft.doc</display_name>
+                  <hidden>true</hidden>
+                  <usage_rights use_justification="creative_commons">
+                    <legal_copyright>(C) 2016 Tommy</legal_copyright>
+                    <license>cc_by_sa</license>
+                  </usage_rights>
+                </file>
+              </files>
+            </fileMeta>
+        "#.as_bytes());
+        let resources = vec![Resource {
+            href: Some("web_resources/example%20folder/Example.DOC".to_string()),
+            identifier: "i97521b4eda791dd993051a519e424e33".to_string(),
+            item_type: ItemType::WebContent,
+            ..Resource::default()
+        }];
+        let mut tree = FileTree::new();
+        tree.insert(FileEntry::new("web_resources/Example Folder/Example.doc"));
+        apply(&meta, &resources, &mut tree);
+
+        let folder = tree.folder("Example Folder").unwrap();
+        assert!(folder.locked);
+        assert_eq!(folder.unlock_at, Some("2016-02-01T07:00:00".to_string()));
+        let file = &folder.files[0];
+        assert_eq!(file.identifier, Some("i97521b4eda791dd993051a519e424e33".to_string()));
+        assert_eq!(file.display_name, Some("Syllabus draft.doc".to_string()));
+        assert!(file.hidden);
+        assert!(!file.locked);
+        let rights = file.usage_rights.as_ref().unwrap();
+        assert_eq!(rights.use_justification, Some("creative_commons".to_string()));
+        assert_eq!(rights.legal_copyright, Some("(C) 2016 Tommy".to_string()));
+        assert_eq!(rights.license, Some("cc_by_sa".to_string()));
+    }
+}
@@ -0,0 +1,51 @@
+use common::{GradingStandard, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Collects each standard with its raw `data` and `version`; `finalize`
+/// turns the data into cutoffs.
+pub struct GradingStandardsHandler {
+    pub standards: Vec<(GradingStandard, Option<String>, String)>,
+    pub stack: Vec<Node>,
+}
+
+impl GradingStandardsHandler {
+    pub fn new() -> GradingStandardsHandler {
+        GradingStandardsHandler {
+            standards: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<(GradingStandard, Option<String>, String)> {
+        self.standards
+    }
+}
+
+impl ParseHandler for GradingStandardsHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        if self.stack.len() == 2 && node.has_name("gradingStandard") {
+            self.standards.push((GradingStandard::new(node.find("identifier")), node.find("version"), String::new()));
+        }
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        if self.stack.len() != 3 || !self.stack[1].has_name("gradingStandard") {
+            return;
+        }
+        let (ref mut standard, _, ref mut data) = match self.standards.last_mut() {
+            Some(entry) => entry,
+            None => return,
+        };
+        match self.stack[2].name_str() {
+            "title" => standard.title.push_str(chars.trim()),
+            "data" => data.push_str(&chars),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,95 @@
+mod handler;
+
+use common::{GradeCutoff, GradingStandard};
+use std::io::Read;
+use summarize::grading_standards::handler::GradingStandardsHandler;
+use summarize::json::Json;
+use summarize::utils::handle_parse;
+
+pub const GRADING_STANDARDS_PATH: &str = "course_settings/grading_standards.xml";
+
+pub fn parse<R: Read>(grading_standards: R) -> Vec<GradingStandard> {
+    let mut handler = GradingStandardsHandler::new();
+    handle_parse(grading_standards, &mut handler);
+    handler
+        .finalize()
+        .into_iter()
+        .map(|(mut standard, version, data)| {
+            standard.cutoffs = parse_cutoffs(&data, version.as_deref() == Some("1"));
+            standard
+        })
+        .collect()
+}
+
+/// `data` is a JSON array like `[["A",0.92],["A-",0.89],…,["F",0.0]]` of
+/// lower bounds. Version 1 standards stored upper bounds instead, which
+/// Canvas converts by taking each bound from the next row.
+fn parse_cutoffs(data: &str, upper_bounds: bool) -> Vec<GradeCutoff> {
+    let rows = match Json::parse(data) {
+        Some(Json::Array(rows)) => rows,
+        _ => return Vec::new(),
+    };
+    let mut cutoffs: Vec<GradeCutoff> = rows
+        .iter()
+        .filter_map(|row| {
+            let row = row.as_array()?;
+            Some(GradeCutoff {
+                name: row.first()?.as_str()?.to_string(),
+                min_score: row.get(1)?.as_f64()?,
+            })
+        })
+        .collect();
+    if upper_bounds && !cutoffs.is_empty() {
+        for i in 0..cutoffs.len() - 1 {
+            cutoffs[i].min_score = cutoffs[i + 1].min_score + 0.01;
+        }
+        if let Some(last) = cutoffs.last_mut() {
+            last.min_score = 0.0;
+        }
+    }
+    cutoffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_standards() {
+        let standards = parse(xml_string().as_bytes());
+        assert_eq!(standards.len(), 2);
+        let standard = &standards[0];
+        assert_eq!(standard.identifier, "i7de25791471f7666845d173c58e99353");
+        assert_eq!(standard.title, "Example Course Grading Scheme");
+        assert_eq!(standard.cutoffs.len(), 5);
+        assert_eq!(standard.cutoffs[1].name, "B");
+        assert_eq!(standard.cutoffs[1].min_score, 0.84);
+        assert_eq!(standard.letter_for(92.0), Some("A"));
+        assert_eq!(standard.letter_for(91.99), Some("B"));
+        assert_eq!(standard.letter_for(-5.0), Some("F"));
+    }
+
+    #[test]
+    fn upgrades_version_one_data() {
+        let standards = parse(xml_string().as_bytes());
+        let cutoffs: Vec<f64> = standards[1].cutoffs.iter().map(|cutoff| cutoff.min_score).collect();
+        assert_eq!(cutoffs, vec![0.6, 0.0]);
+        assert_eq!(standards[1].letter_for(60.0), Some("Pass"));
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <gradingStandards xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+          <gradingStandard identifier="i7de25791471f7666845d173c58e99353" version="2">
+            <title>Example Course Grading Scheme</title>
+            <data>[["A",0.92],["B",0.84],["C",0.74],["D",0.64],["F",0.0]]</data>
+          </gradingStandard>
+          <gradingStandard identifier="i0b5d7c9e0f1a2b3c4d5e6f7a8b9c0d1e" version="1">
+            <title>Pass/Fail</title>
+            <data>[["Pass",1.0],["Fail",0.59]]</data>
+          </gradingStandard>
+        </gradingStandards>
+      "#
+    }
+}
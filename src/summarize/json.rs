@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Just enough JSON to read the small documents Canvas embeds in its XML,
+/// such as `tab_configuration` and grading standard `data`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(BTreeMap<String, Json>),
+}
+
+impl Json {
+    pub fn parse(text: &str) -> Option<Json> {
+        let mut chars = text.chars().peekable();
+        let value = parse_value(&mut chars)?;
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            None => Some(value),
+            Some(_) => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match *self {
+            Json::Array(ref values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Json::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Json::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Json::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match *self {
+            Json::Object(ref map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Strings as-is and numbers without a trailing `.0`, for ids that
+    /// Canvas writes either way.
+    pub fn to_key(&self) -> Option<String> {
+        match *self {
+            Json::String(ref value) => Some(value.clone()),
+            Json::Number(value) if value.fract() == 0.0 => Some(format!("{}", value as i64)),
+            Json::Number(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Compact JSON, with object keys in order.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Number(_) => write!(f, "null"),
+            Json::String(ref value) => write_string(f, value),
+            Json::Array(ref values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(ref map) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Option<Json> {
+    skip_whitespace(chars);
+    match *chars.peek()? {
+        '{' => parse_object(chars),
+        '[' => parse_array(chars),
+        '"' => parse_string(chars).map(Json::String),
+        't' => parse_literal(chars, "true", Json::Bool(true)),
+        'f' => parse_literal(chars, "false", Json::Bool(false)),
+        'n' => parse_literal(chars, "null", Json::Null),
+        _ => parse_number(chars),
+    }
+}
+
+fn parse_literal(chars: &mut Peekable<Chars>, literal: &str, value: Json) -> Option<Json> {
+    for expected in literal.chars() {
+        if chars.next()? != expected {
+            return None;
+        }
+    }
+    Some(value)
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> Option<Json> {
+    let mut text = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E' {
+            text.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    text.parse().ok().map(Json::Number)
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    chars.next();
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'u' => {
+                    let code: String = (0..4).filter_map(|_| chars.next()).collect();
+                    let code = u32::from_str_radix(&code, 16).ok()?;
+                    value.push(::std::char::from_u32(code).unwrap_or('\u{fffd}'));
+                }
+                other => value.push(other),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+fn parse_array(chars: &mut Peekable<Chars>) -> Option<Json> {
+    chars.next();
+    let mut values = Vec::new();
+    skip_whitespace(chars);
+    if chars.peek() == Some(&']') {
+        chars.next();
+        return Some(Json::Array(values));
+    }
+    loop {
+        values.push(parse_value(chars)?);
+        skip_whitespace(chars);
+        match chars.next()? {
+            ',' => continue,
+            ']' => return Some(Json::Array(values)),
+            _ => return None,
+        }
+    }
+}
+
+fn parse_object(chars: &mut Peekable<Chars>) -> Option<Json> {
+    chars.next();
+    let mut map = BTreeMap::new();
+    skip_whitespace(chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+        return Some(Json::Object(map));
+    }
+    loop {
+        skip_whitespace(chars);
+        if chars.peek() != Some(&'"') {
+            return None;
+        }
+        let key = parse_string(chars)?;
+        skip_whitespace(chars);
+        if chars.next()? != ':' {
+            return None;
+        }
+        map.insert(key, parse_value(chars)?);
+        skip_whitespace(chars);
+        match chars.next()? {
+            ',' => continue,
+            '}' => return Some(Json::Object(map)),
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Json;
+
+    #[test]
+    fn parses_nested_values() {
+        let json = Json::parse(r#"[{"id":0},{"id":"context_external_tool_5","hidden":true}, ["A", 0.92], null]"#)
+            .unwrap();
+        let values = json.as_array().unwrap();
+        assert_eq!(values[0].get("id").and_then(|id| id.to_key()), Some("0".to_string()));
+        assert_eq!(values[1].get("id").and_then(|id| id.to_key()), Some("context_external_tool_5".to_string()));
+        assert_eq!(values[1].get("hidden").and_then(|hidden| hidden.as_bool()), Some(true));
+        assert_eq!(values[2], Json::Array(vec![Json::String("A".to_string()), Json::Number(0.92)]));
+        assert_eq!(values[3], Json::Null);
+    }
+
+    #[test]
+    fn writes_documents_back() {
+        let text = r#"{"a":[1,2.5,true,null],"b":"say \"hi\"\n"}"#;
+        let json = Json::parse(text).unwrap();
+        assert_eq!(json.to_string(), text);
+        assert_eq!(Json::parse(&json.to_string()), Some(json));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert_eq!(Json::parse(r#"[1, 2"#), None);
+        assert_eq!(Json::parse(r#"{"a" 1}"#), None);
+        assert_eq!(Json::parse(r#"[1] x"#), None);
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::{Error, ErrorKind, Result};
+
+pub const MANIFEST_NAME: &str = "imsmanifest.xml";
+
+/// Finds the cartridge's manifest among the archive's entries. It's usually
+/// at the root, but a cartridge that was extracted and zipped again keeps it
+/// in a folder, and some tools change its case. The shallowest manifest wins,
+/// as deeper ones belong to packages inside the cartridge.
+pub fn manifest_path(paths: &[String]) -> Result<String> {
+    let candidates: Vec<&String> = paths
+        .iter()
+        .filter(|path| path.rsplit('/').next().unwrap_or(path).eq_ignore_ascii_case(MANIFEST_NAME))
+        .collect();
+    let depth = |path: &str| path.matches('/').count();
+    let shallowest = match candidates.iter().map(|path| depth(path)).min() {
+        Some(shallowest) => shallowest,
+        None => return Err(Error::new(ErrorKind::NotFound, format!("no {} in the cartridge", MANIFEST_NAME))),
+    };
+    let roots: Vec<&str> = candidates
+        .into_iter()
+        .filter(|path| depth(path) == shallowest)
+        .map(String::as_str)
+        .collect();
+    match roots[..] {
+        [path] => Ok(path.to_string()),
+        _ => Err(Error::new(ErrorKind::InvalidData,
+                            format!("several candidate manifests in the cartridge: {}", roots.join(", ")))),
+    }
+}
+
+/// The folder holding the manifest, which the other paths are relative to,
+/// with a trailing slash. Empty at the root.
+pub fn root(manifest_path: &str) -> &str {
+    match manifest_path.rfind('/') {
+        Some(slash) => &manifest_path[..slash + 1],
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{manifest_path, root};
+    use std::io::ErrorKind;
+
+    fn paths(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_the_root_manifest() {
+        let found = manifest_path(&paths(&["course_settings/", "imsmanifest.xml", "unit_1/imsmanifest.xml"])).unwrap();
+        assert_eq!(found, "imsmanifest.xml");
+        assert_eq!(root(&found), "");
+        let found = manifest_path(&paths(&["My Course/", "My Course/IMSManifest.xml", "My Course/web_resources/a.jpg"]))
+            .unwrap();
+        assert_eq!(found, "My Course/IMSManifest.xml");
+        assert_eq!(root(&found), "My Course/");
+    }
+
+    #[test]
+    fn rejects_missing_and_ambiguous_manifests() {
+        let error = manifest_path(&paths(&["web_resources/a.jpg"])).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        let error = manifest_path(&paths(&["a/imsmanifest.xml", "b/imsmanifest.xml"])).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("a/imsmanifest.xml, b/imsmanifest.xml"));
+    }
+}
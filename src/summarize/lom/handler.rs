@@ -0,0 +1,187 @@
+use common::{LangString, Lom, LomClassification, LomContributor, LomDuration, LomIdentifier, LomTaxon,
+             LomTaxonPath, ParseHandler, VCard};
+use common::lom::vocabulary_flag;
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Collects a `<lom>` element into a `Lom`. Element paths are matched
+/// relative to the `<lom>` root, so the same handler serves manifest-level
+/// and resource-level metadata.
+pub struct LomHandler {
+    pub lom: Lom,
+    pub stack: Vec<Node>,
+}
+
+impl LomHandler {
+    pub fn new() -> LomHandler {
+        LomHandler {
+            lom: Lom::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> Lom {
+        self.lom
+    }
+
+    fn start_element(&mut self) {
+        let lom = &mut self.lom;
+        // Paths are matched below the `<lom>` root.
+        match Node::path(&self.stack).get(1..).unwrap_or_default() {
+            ["general", "identifier"] => lom.general.identifiers.push(LomIdentifier::default()),
+            ["general", "keyword"] => lom.general.keywords.push(LangString::new()),
+            ["general", "coverage"] => lom.general.coverage.push(LangString::new()),
+            ["lifeCycle", "contribute"] => lom.life_cycle.contributors.push(LomContributor::default()),
+            ["educational", "typicalLearningTime"] => {
+                lom.educational.typical_learning_time = Some(LomDuration::default())
+            }
+            ["classification"] => lom.classifications.push(LomClassification::default()),
+            ["classification", "keyword"] => {
+                if let Some(classification) = lom.classifications.last_mut() {
+                    classification.keywords.push(LangString::new());
+                }
+            }
+            ["classification", "taxonPath"] => {
+                if let Some(classification) = lom.classifications.last_mut() {
+                    classification.taxon_paths.push(LomTaxonPath::default());
+                }
+            }
+            ["classification", "taxonPath", "taxon"] => {
+                if let Some(path) = lom.classifications.last_mut().and_then(|c| c.taxon_paths.last_mut()) {
+                    path.taxa.push(LomTaxon::default());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ParseHandler for LomHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node);
+        self.start_element();
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let language = match self.stack.last() {
+            Some(node) if node.has_name("string") => node.find("language"),
+            Some(_) => None,
+            None => return,
+        };
+        let lom = &mut self.lom;
+        match Node::path(&self.stack).get(1..).unwrap_or_default() {
+            ["general", "identifier", "catalog"] => {
+                if let Some(identifier) = lom.general.identifiers.last_mut() {
+                    identifier.catalog = chars;
+                }
+            }
+            ["general", "identifier", "entry"] => {
+                if let Some(identifier) = lom.general.identifiers.last_mut() {
+                    identifier.entry = chars;
+                }
+            }
+            ["general", "title", "string"] => {
+                lom.general.title.insert(language, chars);
+            }
+            ["general", "language"] => lom.general.languages.push(chars.trim().to_string()),
+            ["general", "description", "string"] => {
+                lom.general.description.insert(language, chars);
+            }
+            ["general", "keyword", "string"] => {
+                if let Some(keyword) = lom.general.keywords.last_mut() {
+                    keyword.insert(language, chars);
+                }
+            }
+            ["general", "coverage", "string"] => {
+                if let Some(coverage) = lom.general.coverage.last_mut() {
+                    coverage.insert(language, chars);
+                }
+            }
+            ["lifeCycle", "version", "string"] => {
+                lom.life_cycle.version.insert(language, chars);
+            }
+            ["lifeCycle", "status", "value"] => lom.life_cycle.status = Some(chars.trim().to_string()),
+            ["lifeCycle", "contribute", "role", "value"] => {
+                if let Some(contributor) = lom.life_cycle.contributors.last_mut() {
+                    contributor.role = Some(chars.trim().to_string());
+                }
+            }
+            ["lifeCycle", "contribute", "entity"] => {
+                if let Some(contributor) = lom.life_cycle.contributors.last_mut() {
+                    contributor.entities.push(VCard::parse(&chars));
+                }
+            }
+            ["lifeCycle", "contribute", "date", "dateTime"] => {
+                if let Some(contributor) = lom.life_cycle.contributors.last_mut() {
+                    contributor.date = Some(chars.trim().to_string());
+                }
+            }
+            ["educational", "intendedEndUserRole", "value"] => {
+                lom.educational.intended_end_user_roles.push(chars.trim().to_string())
+            }
+            ["educational", "context", "value"] => lom.educational.contexts.push(chars.trim().to_string()),
+            ["educational", "typicalLearningTime", "duration"] => {
+                if let Some(ref mut time) = lom.educational.typical_learning_time {
+                    time.duration = chars.trim().to_string();
+                }
+            }
+            ["educational", "typicalLearningTime", "description", "string"] => {
+                if let Some(ref mut time) = lom.educational.typical_learning_time {
+                    time.description.insert(language, chars);
+                }
+            }
+            ["educational", "description", "string"] => {
+                lom.educational.description.insert(language, chars);
+            }
+            ["rights", "cost", "value"] => lom.rights.cost = vocabulary_flag(&chars),
+            ["rights", "copyrightAndOtherRestrictions", "value"] => {
+                lom.rights.copyright_and_other_restrictions = vocabulary_flag(&chars)
+            }
+            ["rights", "description", "string"] => {
+                lom.rights.description.insert(language, chars);
+            }
+            ["classification", "purpose", "value"] => {
+                if let Some(classification) = lom.classifications.last_mut() {
+                    classification.purpose = Some(chars.trim().to_string());
+                }
+            }
+            ["classification", "description", "string"] => {
+                if let Some(classification) = lom.classifications.last_mut() {
+                    classification.description.insert(language, chars);
+                }
+            }
+            ["classification", "keyword", "string"] => {
+                if let Some(keyword) = lom.classifications.last_mut().and_then(|c| c.keywords.last_mut()) {
+                    keyword.insert(language, chars);
+                }
+            }
+            ["classification", "taxonPath", "source", "string"] => {
+                if let Some(path) = lom.classifications.last_mut().and_then(|c| c.taxon_paths.last_mut()) {
+                    path.source.insert(language, chars);
+                }
+            }
+            ["classification", "taxonPath", "taxon", "id"] => {
+                if let Some(taxon) = last_taxon(lom) {
+                    taxon.id = chars.trim().to_string();
+                }
+            }
+            ["classification", "taxonPath", "taxon", "entry", "string"] => {
+                if let Some(taxon) = last_taxon(lom) {
+                    taxon.entry.insert(language, chars);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn last_taxon(lom: &mut Lom) -> Option<&mut LomTaxon> {
+    lom.classifications
+        .last_mut()
+        .and_then(|classification| classification.taxon_paths.last_mut())
+        .and_then(|path| path.taxa.last_mut())
+}
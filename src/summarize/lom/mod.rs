@@ -0,0 +1,141 @@
+pub mod handler;
+
+#[cfg(test)]
+mod tests {
+    use common::Lom;
+    use summarize::lom::handler::LomHandler;
+    use summarize::utils::handle_parse;
+
+    fn parse(metadata: &[u8]) -> Lom {
+        let mut handler = LomHandler::new();
+        handle_parse(metadata, &mut handler);
+        handler.finalize()
+    }
+
+    #[test]
+    fn parses_general() {
+        let lom = parse(xml_string().as_bytes());
+        assert_eq!(lom.general.title.value(), "Week 1 Answer Key");
+        assert_eq!(lom.general.languages, vec!["en"]);
+        assert_eq!(lom.general.keywords.len(), 2);
+        assert_eq!(lom.general.keywords[1].get("fr"), Some("réponses"));
+        assert_eq!(lom.general.coverage[0].value(), "Grade 9");
+        assert_eq!(lom.general.identifiers[0].entry, "answer-key-1");
+    }
+
+    #[test]
+    fn parses_life_cycle() {
+        let lom = parse(xml_string().as_bytes());
+        let contributors = &lom.life_cycle.contributors;
+        assert_eq!(contributors.len(), 1);
+        assert_eq!(contributors[0].role, Some("author".to_string()));
+        assert_eq!(contributors[0].date, Some("2016-03-15".to_string()));
+        assert_eq!(contributors[0].entities[0].formatted_name, Some("Tommy Groshong".to_string()));
+        assert_eq!(contributors[0].entities[0].email, Some("tommy@example.com".to_string()));
+    }
+
+    #[test]
+    fn parses_educational_and_rights() {
+        let lom = parse(xml_string().as_bytes());
+        assert_eq!(lom.educational.intended_end_user_roles, vec!["Instructor"]);
+        assert_eq!(lom.educational.contexts, vec!["higher education"]);
+        let time = lom.educational.typical_learning_time.unwrap();
+        assert_eq!(time.duration, "PT1H30M");
+        assert_eq!(lom.rights.cost, Some(false));
+        assert_eq!(lom.rights.copyright_and_other_restrictions, Some(true));
+    }
+
+    #[test]
+    fn parses_classification() {
+        let lom = parse(xml_string().as_bytes());
+        assert_eq!(lom.classifications.len(), 1);
+        let classification = &lom.classifications[0];
+        assert_eq!(classification.purpose, Some("discipline".to_string()));
+        assert_eq!(classification.taxon_paths[0].source.value(), "ACM CCS");
+        assert_eq!(classification.taxon_paths[0].taxa.len(), 2);
+        assert_eq!(classification.taxon_paths[0].taxa[1].id, "D.3");
+        assert_eq!(classification.taxon_paths[0].taxa[1].entry.value(), "Programming Languages");
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <lom xmlns="http://ltsc.ieee.org/xsd/imsccv1p1/LOM/resource">
+          <general>
+            <identifier>
+              <catalog>URI</catalog>
+              <entry>answer-key-1</entry>
+            </identifier>
+            <title>
+              <string language="en">Week 1 Answer Key</string>
+            </title>
+            <language>en</language>
+            <keyword>
+              <string language="en">key</string>
+            </keyword>
+            <keyword>
+              <string language="en">answers</string>
+              <string language="fr">réponses</string>
+            </keyword>
+            <coverage>
+              <string>Grade 9</string>
+            </coverage>
+          </general>
+          <lifeCycle>
+            <contribute>
+              <role>
+                <source>LOMv1.0</source>
+                <value>author</value>
+              </role>
+              <entity><![CDATA[BEGIN:VCARD
+VERSION:3.0
+FN:Tommy Groshong
+EMAIL;TYPE=INTERNET:tommy@example.com
+END:VCARD]]></entity>
+              <date>
+                <dateTime>2016-03-15</dateTime>
+              </date>
+            </contribute>
+          </lifeCycle>
+          <educational>
+            <intendedEndUserRole>
+              <source>IMSGLC_CC_Rolesv1p1</source>
+              <value>Instructor</value>
+            </intendedEndUserRole>
+            <context>
+              <value>higher education</value>
+            </context>
+            <typicalLearningTime>
+              <duration>PT1H30M</duration>
+            </typicalLearningTime>
+          </educational>
+          <rights>
+            <cost>
+              <value>no</value>
+            </cost>
+            <copyrightAndOtherRestrictions>
+              <value>yes</value>
+            </copyrightAndOtherRestrictions>
+          </rights>
+          <classification>
+            <purpose>
+              <value>discipline</value>
+            </purpose>
+            <taxonPath>
+              <source>
+                <string>ACM CCS</string>
+              </source>
+              <taxon>
+                <id>D</id>
+                <entry><string>Software</string></entry>
+              </taxon>
+              <taxon>
+                <id>D.3</id>
+                <entry><string>Programming Languages</string></entry>
+              </taxon>
+            </taxonPath>
+          </classification>
+        </lom>
+      "#
+    }
+}
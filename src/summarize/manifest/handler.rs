@@ -1,20 +1,56 @@
-use common::{ Manifest, ManifestBuilder, ModuleBuilder, ModuleItemBuilder, Resource, ParseHandler };
-use summarize::utils::{Node, MODULE_DEPTH, MODULE_ITEM_DEPTH};
+use common::{ join_base, normalize_href, resolve_href, Manifest, ManifestBuilder, ModuleBuilder, ModuleItemBuilder, Organization, OrganizationItem,
+              Resource, ParseHandler };
+use summarize::lom::handler::LomHandler;
+use summarize::namespaces::{Matching, Namespace};
+use summarize::utils::{Node, MODULE_DEPTH};
 use super::index_tracker::ModuleIndexTracker;
 use xml::name::OwnedName;
 
 pub struct ManifestHandler {
     pub builder: ManifestBuilder,
     pub index_tracker: ModuleIndexTracker,
+    pub module_depth: usize,
+    /// Set from the `<manifest>` element's namespace.
+    pub matching: Matching,
     pub stack: Vec<Node>,
+    /// Receives events while inside a `<lom>` element, along with the depth
+    /// of that element and the resource it describes, if any.
+    pub lom: Option<(LomHandler, usize, Option<String>)>,
+    pub current_resource: Option<String>,
+    /// Whether an `<organization>` is open, and whether it's the one the
+    /// modules are built from.
+    pub in_organization: bool,
+    pub builds_modules: bool,
+    /// The organization to build modules from, when already known.
+    pub modules_organization: Option<String>,
+    /// The open items of the current organization.
+    pub organization_items: Vec<OrganizationItem>,
+    /// Receives events while inside a nested `<manifest>`, along with the
+    /// depth of that element.
+    pub sub_manifest: Option<(Box<ManifestHandler>, usize)>,
+    /// The `xml:base` in effect where this manifest starts, for sub-manifests.
+    pub base: String,
 }
 
 impl ManifestHandler {
-    pub fn new() -> ManifestHandler {
+    /// `wraps_modules` says whether the modules sit under a root item; see
+    /// `Organization::wraps_modules`.
+    pub fn new(wraps_modules: bool) -> ManifestHandler {
+        let module_depth = if wraps_modules { MODULE_DEPTH } else { MODULE_DEPTH - 1 };
         ManifestHandler {
             builder: ManifestBuilder::new(),
-            index_tracker: ModuleIndexTracker::new(),
+            index_tracker: ModuleIndexTracker::new(module_depth),
+            module_depth,
+            matching: Matching::Strict,
             stack: Vec::new(),
+            lom: None,
+            current_resource: None,
+            in_organization: false,
+            builds_modules: false,
+            modules_organization: None,
+            organization_items: Vec::new(),
+            sub_manifest: None,
+            base: String::new(),
         }
     }
 
@@ -22,21 +58,119 @@ impl ManifestHandler {
         self.builder.finalize()
     }
 
-    fn new_module_builder(&mut self) {
-        self.builder.modules.push(ModuleBuilder::new());
+    /// Modules are built from `modules_organization` when it's set, else
+    /// from the organization named as the default, or the first one when
+    /// none is.
+    fn new_organization(&mut self, node: &Node) {
+        let organization = Organization {
+            structure: node.find("structure"),
+            ..Organization::new(node.find("identifier"))
+        };
+        self.builds_modules = match self.modules_organization.as_ref().or(self.builder.default_organization.as_ref()) {
+            Some(identifier) => *identifier == organization.identifier,
+            None => self.builder.organizations.is_empty(),
+        };
+        self.in_organization = true;
+        self.builder.organizations.push(organization);
     }
 
-    fn new_module_item_builder(&mut self, node: &Node) {
+    fn close_organization_item(&mut self) {
+        if let Some(item) = self.organization_items.pop() {
+            if let Some(parent) = self.organization_items.last_mut() {
+                parent.items.push(item);
+            } else if let Some(organization) = self.builder.organizations.last_mut() {
+                organization.items.push(item);
+            }
+        }
+    }
+
+    fn finish_sub_manifest(&mut self) {
+        if let Some((handler, _)) = self.sub_manifest.take() {
+            self.builder.sub_manifests.push(handler.finalize_manifest());
+        }
+    }
+
+    fn new_module_builder(&mut self, node: &Node) {
+        self.builder.modules.push(ModuleBuilder::new(node.find("identifier")));
+    }
+
+    /// Items inside items, like the files of a folder, join the module's
+    /// items after their parent, indented one more level.
+    fn new_module_item_builder(&mut self, node: &Node, depth: usize) {
         if let Some(module) = self.builder.modules.get_mut(self.index_tracker.module_index) {
-            module.items.push(ModuleItemBuilder::new(node.find("identifierref")));
+            let mut item = ModuleItemBuilder::new(node.find("identifier"), node.find("identifierref"));
+            item.indent = (depth - self.module_depth - 1) as u32;
+            module.items.push(item);
+            self.index_tracker.open_item(module.items.len() - 1);
+        }
+    }
+
+    /// The `xml:base` of the element at `depth`, nesting those of its
+    /// ancestors.
+    fn xml_base(&self, depth: usize) -> String {
+        self.stack[..depth]
+            .iter()
+            .filter_map(Node::xml_base)
+            .fold(self.base.clone(), |base, child| join_base(&base, child))
+    }
+
+    fn add_resource_file(&mut self, node: &Node) {
+        let base = self.xml_base(self.stack.len());
+        let resources = &mut self.builder.resources_map;
+        let resource = self.current_resource.as_ref().and_then(|identifier| resources.get_mut(identifier));
+        if let (Some(resource), Some(href)) = (resource, node.find("href")) {
+            resource.files.push(resolve_href(&base, &href));
         }
     }
 
     fn new_resource(&mut self, node: &Node) {
-        let resource = Resource::new(node);
+        let base = self.xml_base(self.stack.len());
+        let mut resource = Resource::new(node);
+        resource.href = resource.href.map(|href| resolve_href(&base, &href));
+        resource.extension_file = resource.extension_file.map(|file| normalize_href(&file));
+        self.current_resource = Some(resource.identifier.clone());
         self.builder.resources_map.insert(resource.identifier.clone(), resource);
     }
 
+    /// Starts collecting metadata if `node` is the manifest's or a resource's
+    /// `<lom>` element.
+    fn start_lom(&mut self, node: &Node) -> bool {
+        let depth = self.stack.len();
+        let cp = Namespace::ContentPackaging;
+        if depth < 3 || !self.matching.matches(&self.stack[depth - 2], cp, "metadata") {
+            return false;
+        }
+        let owner = &self.stack[depth - 3];
+        let resource = if self.matching.matches(owner, cp, "manifest") &&
+                          self.matching.matches(node, Namespace::ManifestLom, "lom") {
+            None
+        } else if self.matching.matches(owner, cp, "resource") &&
+                  self.matching.matches(node, Namespace::ResourceLom, "lom") &&
+                  self.current_resource.is_some() {
+            self.current_resource.clone()
+        } else {
+            return false;
+        };
+        let mut handler = LomHandler::new();
+        handler.enter(node.clone());
+        self.lom = Some((handler, depth, resource));
+        true
+    }
+
+    fn finish_lom(&mut self) {
+        if let Some((handler, _, resource)) = self.lom.take() {
+            let lom = handler.finalize();
+            match resource {
+                Some(identifier) => {
+                    if let Some(resource) = self.builder.resources_map.get_mut(&identifier) {
+                        resource.set_metadata(lom);
+                    }
+                }
+                None => self.builder.metadata = lom,
+            }
+        }
+    }
+
     fn add_module_title(&mut self, chars: String) {
         let module_index = self.index_tracker.module_index;
         if let Some(module) = self.builder.modules.get_mut(module_index) {
@@ -46,7 +180,10 @@ impl ManifestHandler {
 
     fn add_module_item_title(&mut self, chars: String) {
         let module_index = self.index_tracker.module_index;
-        let module_item_index = self.index_tracker.module_item_index;
+        let module_item_index = match self.index_tracker.current_item() {
+            Some(index) => index,
+            None => return,
+        };
         if let Some(module) = self.builder.modules.get_mut(module_index) {
             if let Some(module_item) = module.items.get_mut(module_item_index) {
                 module_item.title(chars);
@@ -59,77 +196,122 @@ impl ParseHandler for ManifestHandler {
     fn enter(&mut self, node: Node) {
         self.stack.push(node.clone());
         let depth = self.stack.len();
-        match node.name_str() {
-            "item" => {
-                if depth == MODULE_DEPTH {
-                    self.new_module_builder();
-                } else if depth == MODULE_ITEM_DEPTH {
-                    self.new_module_item_builder(&node);
-                }
-            }
-            "resource" => {
-                if depth == 3 {
-                    self.new_resource(&node);
-                }
-            }
-            _ => {}
+        if let Some((ref mut handler, _)) = self.sub_manifest {
+            handler.enter(node);
+            return;
+        }
+        if let Some((ref mut handler, _, _)) = self.lom {
+            handler.enter(node);
+            return;
+        }
+        if self.start_lom(&node) {
+            return;
+        }
+        if depth == 1 && node.has_name("manifest") {
+            self.matching = Matching::for_manifest(&node);
+            self.builder.identifier = node.find("identifier").unwrap_or_default();
+            return;
+        }
+        let is = |name: &str| self.matching.matches(&node, Namespace::ContentPackaging, name);
+        if is("manifest") {
+            let mut handler = ManifestHandler::new(self.module_depth == MODULE_DEPTH);
+            handler.base = self.xml_base(depth - 1);
+            handler.enter(node);
+            self.sub_manifest = Some((Box::new(handler), depth));
+            return;
+        }
+        let is_item = is("item");
+        if is("organizations") && depth == 2 {
+            self.builder.default_organization = node.find("default");
+        } else if is("organization") && depth == 3 {
+            self.new_organization(&node);
+        } else if is_item && self.builds_modules && depth == self.module_depth {
+            self.new_module_builder(&node);
+        } else if is_item && self.builds_modules && depth > self.module_depth {
+            self.new_module_item_builder(&node, depth);
+        } else if is("resource") && depth == 3 {
+            self.new_resource(&node);
+        } else if is("file") && depth == 4 {
+            self.add_resource_file(&node);
+        }
+        if is_item && self.in_organization {
+            self.organization_items.push(OrganizationItem::new(node.find("identifier"), node.find("identifierref")));
         }
     }
 
     fn leave(&mut self,  name: OwnedName) {
+        let depth = self.stack.len();
         self.stack.pop();
-        if name.local_name.as_str() == "item" {
-            self.index_tracker.step(self.stack.len());
+        if let Some((ref mut handler, manifest_depth)) = self.sub_manifest {
+            handler.leave(name);
+            if depth == manifest_depth {
+                self.finish_sub_manifest();
+            }
+            return;
+        }
+        if let Some((ref mut handler, lom_depth, _)) = self.lom {
+            handler.leave(name);
+            if depth == lom_depth {
+                self.finish_lom();
+            }
+            return;
+        }
+        let is = |local_name: &str| self.matching.matches_name(&name, Namespace::ContentPackaging, local_name);
+        let is_item = is("item");
+        if is_item && self.builds_modules {
+            self.index_tracker.step(depth);
+        } else if is("organization") && depth == 3 {
+            self.in_organization = false;
+            self.builds_modules = false;
+        } else if is("resource") {
+            self.current_resource = None;
+        }
+        if is_item && self.in_organization {
+            self.close_organization_item();
         }
     }
 
     fn receive_chars(&mut self, chars: String) {
-        let num_ancestors = self.stack.len();
-        if num_ancestors < 2 {
+        if let Some((ref mut handler, _)) = self.sub_manifest {
+            handler.receive_chars(chars);
             return;
         }
-
-        if self.stack.last().unwrap().has_name("title")
-            && self.stack.get(num_ancestors - 2).unwrap().has_name("item") {
-            attach_titles(self, chars);
+        if let Some((ref mut handler, _, _)) = self.lom {
+            handler.receive_chars(chars);
             return;
         }
 
-        if num_ancestors < 4 {
+        let num_ancestors = self.stack.len();
+        if num_ancestors < 2 {
             return;
         }
 
-        if self.stack.get(num_ancestors - 4).unwrap().has_name("lom") {
-            find_general_data(self, chars);
+        let cp = Namespace::ContentPackaging;
+        if !self.matching.matches(&self.stack[num_ancestors - 1], cp, "title") {
             return;
         }
+        let parent = &self.stack[num_ancestors - 2];
+        if self.matching.matches(parent, cp, "item") {
+            attach_titles(self, chars);
+        } else if self.matching.matches(parent, cp, "organization") {
+            if let Some(organization) = self.builder.organizations.last_mut() {
+                organization.title = Some(chars);
+            }
+        }
     }
 }
 
 fn attach_titles(handler: &mut ManifestHandler, chars: String) {
+    if let Some(item) = handler.organization_items.last_mut() {
+        item.title = chars.clone();
+    }
+    if !handler.builds_modules {
+        return;
+    }
     let depth = handler.stack.len();
-    if depth == MODULE_DEPTH + 1 {
+    if depth == handler.module_depth + 1 {
         handler.add_module_title(chars)
-    } else if depth == MODULE_ITEM_DEPTH + 1 {
+    } else if depth > handler.module_depth + 1 {
         handler.add_module_item_title(chars)
     }
 }
-
-fn find_general_data(handler: &mut ManifestHandler, chars: String) {
-    let num_ancestors = handler.stack.len();
-    let ref current_tag = handler.stack.last().unwrap();
-    let ref parent_tag = handler.stack.get(num_ancestors - 2).unwrap();
-    let ref category = handler.stack.get(num_ancestors - 3).unwrap();
-
-    if category.has_name("general") {
-        if parent_tag.has_name("title") && current_tag.has_name("string") {
-            handler.builder.general.title(chars);
-        } else if parent_tag.has_name("description") && current_tag.has_name("string") {
-            handler.builder.general.description(chars);
-        }
-    } else if category.has_name("rights") {
-        if parent_tag.has_name("description") && current_tag.has_name("string") {
-            handler.builder.general.copyright(chars);
-        }
-    }
-}
@@ -1,28 +1,35 @@
-use summarize::utils::{MODULE_DEPTH, MODULE_ITEM_DEPTH};
-
+/// Tracks which module, and which of its items, the parser is inside.
 pub struct ModuleIndexTracker {
+    pub module_depth: usize,
     pub module_index: usize,
-    pub module_item_index: usize,
+    /// Indexes into the module's items of the items currently open, from
+    /// the outermost; folders nest items inside items.
+    pub open_items: Vec<usize>,
 }
 
 impl ModuleIndexTracker {
-    pub fn new() -> ModuleIndexTracker {
+    pub fn new(module_depth: usize) -> ModuleIndexTracker {
         ModuleIndexTracker {
+            module_depth,
             module_index: 0,
-            module_item_index: 0,
+            open_items: Vec::new(),
         }
     }
 
+    pub fn open_item(&mut self, index: usize) {
+        self.open_items.push(index);
+    }
+
+    pub fn current_item(&self) -> Option<usize> {
+        self.open_items.last().cloned()
+    }
+
     pub fn step(&mut self, depth: usize) {
-        match depth {
-            MODULE_DEPTH => {
-                self.module_index += 1;
-                self.module_item_index = 0;
-            }
-            MODULE_ITEM_DEPTH => {
-                self.module_item_index += 1;
-            }
-            _ => {}
+        if depth == self.module_depth {
+            self.module_index += 1;
+            self.open_items.clear();
+        } else if depth > self.module_depth {
+            self.open_items.pop();
         }
     }
 }
@@ -1,29 +1,94 @@
 mod handler;
 mod index_tracker;
 
-use common::{ Manifest };
-use std::io::{ Read };
+use common::Manifest;
 use summarize::manifest::handler::ManifestHandler;
 use summarize::utils::handle_parse;
 
-pub fn parse<R: Read>(manifest: R) -> Manifest {
-    let mut handler = ManifestHandler::new();
-    handle_parse(manifest, &mut handler);
-    let manifest = handler.finalize_manifest();
+/// Reads the manifest once, then again when the modules came out of the
+/// wrong organization or the wrong depth: they're built from the default
+/// organization, or the first when the default names none, and sit under a
+/// root item only when that organization is shaped that way.
+pub fn parse(manifest: &[u8]) -> Manifest {
+    let first = read(manifest, true, None);
+    let manifest = match misread_modules(&first) {
+        Some((identifier, wraps_modules)) => read(manifest, wraps_modules, Some(identifier)),
+        None => first,
+    };
     println!("{:?}", &manifest.general);
     manifest
 }
 
+/// The organization to build the modules from and whether it wraps them,
+/// when the first reading got either wrong.
+fn misread_modules(manifest: &Manifest) -> Option<(String, bool)> {
+    let named = manifest.default_organization.as_ref().and_then(|default| {
+        manifest.organizations.iter().find(|organization| organization.identifier == *default)
+    });
+    let missing_default = manifest.default_organization.is_some() && named.is_none();
+    named
+        .or(manifest.organizations.first())
+        .filter(|organization| missing_default || !organization.wraps_modules())
+        .map(|organization| (organization.identifier.clone(), organization.wraps_modules()))
+}
+
+fn read(manifest: &[u8], wraps_modules: bool, modules_organization: Option<String>) -> Manifest {
+    let mut handler = ManifestHandler::new(wraps_modules);
+    handler.modules_organization = modules_organization;
+    handle_parse(manifest, &mut handler);
+    handler.finalize_manifest()
+}
+
 #[cfg(test)]
 mod tests {
     use super::parse;
+    use common::{Audience, IntendedUse, ItemType};
 
     #[test]
     fn parses_general() {
         let manifest = parse(xml_string().as_bytes());
-        assert_eq!(manifest.general.title, "A Course");
-        assert_eq!(manifest.general.description, "test this course");
-        assert_eq!(manifest.general.copyright, "Private");
+        assert_eq!(manifest.identifier, "i3ebf4e9fe673c98a2e10715ec293b6bf");
+        assert_eq!(manifest.general.title.value(), "A Course");
+        assert_eq!(manifest.general.description.value(), "test this course");
+        assert_eq!(manifest.general.copyright.value(), "Private");
+    }
+
+    #[test]
+    fn parses_manifest_metadata() {
+        let manifest = parse(xml_string().as_bytes());
+        let contributors = &manifest.metadata.life_cycle.contributors;
+        assert_eq!(contributors.len(), 1);
+        assert_eq!(contributors[0].date, Some("2016-03-15".to_string()));
+        assert_eq!(manifest.metadata.rights.copyright_and_other_restrictions, Some(true));
+        assert_eq!(manifest.general.keywords.len(), 1);
+        assert_eq!(manifest.general.keywords[0].value(), "testing");
+    }
+
+    #[test]
+    fn parses_resource_metadata() {
+        let manifest = parse(xml_string().as_bytes());
+        let resource = manifest.resources
+            .iter()
+            .find(|resource| resource.identifier == "iadc3bb46492a88f5dec93121538151df")
+            .unwrap();
+        let metadata = resource.metadata.as_ref().unwrap();
+        assert_eq!(metadata.educational.intended_end_user_roles, vec!["Instructor"]);
+        assert_eq!(metadata.general.title.value(), "Teacher notes");
+        assert_eq!(resource.audience, Audience::Instructor);
+        assert!(manifest.resources.iter().filter(|resource| resource.metadata.is_some()).count() == 1);
+        assert_eq!(manifest.general.title.value(), "A Course");
+    }
+
+    #[test]
+    fn parses_multilingual_general() {
+        let manifest = parse(multilingual_xml_string().as_bytes());
+        let title = &manifest.general.title;
+        assert_eq!(title.languages(), vec!["en", "fr-CA"]);
+        assert_eq!(title.get("fr"), Some("Un cours"));
+        assert_eq!(title.preferred(&["fr-CA", "en"]), "Un cours");
+        assert_eq!(title.preferred(&["de"]), "A Course");
+        assert_eq!(manifest.general.description.get("en-US"), Some("test this course"));
+        assert_eq!(manifest.general.copyright.get("fr"), Some("Privé"));
     }
 
     #[test]
@@ -33,10 +98,262 @@ mod tests {
         assert!(manifest.modules.iter().any(|module| module.title == "Mod Testing" || module.title == "Module 1"));
     }
 
+    #[test]
+    fn parses_module_items() {
+        let manifest = parse(xml_string().as_bytes());
+        assert_eq!(manifest.modules[0].items.len(), 1);
+        let items = &manifest.modules[1].items;
+        let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, vec!["Week 1", "Assignment 1", "Quiz 1"]);
+        assert_eq!(items[0].item_type, ItemType::SubHeader);
+        assert_eq!(items[1].item_type, ItemType::Assignment);
+        assert_eq!(items[2].item_type, ItemType::Assessment);
+    }
+
     #[test]
     fn parses_resources() {
         let manifest = parse(xml_string().as_bytes());
         assert_eq!(manifest.resources.len(), 9);
+        let intended_use = |identifier: &str| {
+            manifest.resources.iter().find(|resource| resource.identifier == identifier).unwrap().intended_use
+        };
+        assert_eq!(intended_use("i5e0d8279664539be677db96c71643966_syllabus"), IntendedUse::Syllabus);
+        assert_eq!(intended_use("i5e0d8279664539be677db96c71643966"), IntendedUse::Unspecified);
+    }
+
+    #[test]
+    fn parses_modules_without_a_root_item() {
+        let manifest = parse(r#"
+            <manifest identifier="M_1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+              <organizations>
+                <organization identifier="org_1" structure="rooted-hierarchy">
+                  <item identifier="section_0">
+                    <title>General</title>
+                    <item identifier="item_1" identifierref="res_1"><title>Welcome</title></item>
+                  </item>
+                  <item identifier="section_1">
+                    <title>Week 1</title>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource identifier="res_1" type="webcontent" href="welcome.html"/>
+              </resources>
+            </manifest>
+        "#.as_bytes());
+        assert_eq!(manifest.modules.len(), 2);
+        assert_eq!(manifest.modules[0].title, "General");
+        assert_eq!(manifest.modules[0].items[0].title, "Welcome");
+        assert_eq!(manifest.modules[0].items[0].item_type, ItemType::WebContent);
+        assert_eq!(manifest.modules[1].title, "Week 1");
+    }
+
+    #[test]
+    fn ignores_vendor_extension_elements() {
+        let manifest = parse(r#"
+            <manifest identifier="m1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1"
+                      xmlns:lomimscc="http://ltsc.ieee.org/xsd/imsccv1p1/LOM/manifest"
+                      xmlns:ext="http://vendor.example.com/cc/extensions">
+              <metadata>
+                <lomimscc:lom>
+                  <lomimscc:general>
+                    <lomimscc:title><lomimscc:string>A Course</lomimscc:string></lomimscc:title>
+                    <ext:title><lomimscc:string>Internal code 1234</lomimscc:string></ext:title>
+                  </lomimscc:general>
+                </lomimscc:lom>
+              </metadata>
+              <organizations>
+                <organization identifier="org_1" structure="rooted-hierarchy">
+                  <item identifier="root">
+                    <item identifier="m1">
+                      <title>Module 1</title>
+                      <ext:title>Hidden vendor title</ext:title>
+                      <ext:item identifier="vendor_item"><title>Vendor item</title></ext:item>
+                      <item identifier="i1" identifierref="r1"><title>Page</title></item>
+                    </item>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource ext:identifier="other" ext:file="r1.dat" identifier="r1" type="webcontent" href="page.html"/>
+              </resources>
+            </manifest>
+        "#.as_bytes());
+        assert_eq!(manifest.general.title.value(), "A Course");
+        assert_eq!(manifest.modules.len(), 1);
+        assert_eq!(manifest.modules[0].title, "Module 1");
+        assert_eq!(manifest.modules[0].items.len(), 1);
+        assert_eq!(manifest.modules[0].items[0].title, "Page");
+        assert_eq!(manifest.resources[0].identifier, "r1");
+        assert_eq!(manifest.resources[0].extension_file, None);
+    }
+
+    #[test]
+    fn reads_manifests_without_namespaces() {
+        let manifest = parse(r#"
+            <manifest identifier="m1">
+              <organizations>
+                <organization identifier="org_1">
+                  <item identifier="root">
+                    <item identifier="m1">
+                      <title>Module 1</title>
+                      <item identifier="i1" identifierref="r1"><title>Page</title></item>
+                    </item>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource identifier="r1" type="webcontent" href="page.html"/>
+              </resources>
+            </manifest>
+        "#.as_bytes());
+        assert_eq!(manifest.modules[0].items[0].title, "Page");
+        assert_eq!(manifest.modules[0].items[0].item_type, ItemType::WebContent);
+    }
+
+    #[test]
+    fn parses_organizations_and_sub_manifests() {
+        let manifest = parse(r#"
+            <manifest identifier="course" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+              <organizations default="by_topic">
+                <organization identifier="by_week" structure="rooted-hierarchy">
+                  <title>By week</title>
+                  <item identifier="root_1">
+                    <item identifier="week_1">
+                      <title>Week 1</title>
+                      <item identifier="i1" identifierref="r1"><title>Intro</title></item>
+                    </item>
+                  </item>
+                </organization>
+                <organization identifier="by_topic" structure="rooted-hierarchy">
+                  <item identifier="root_2">
+                    <item identifier="topic_1">
+                      <title>Topic 1</title>
+                      <item identifier="i2" identifierref="r1"><title>Intro</title></item>
+                      <item identifier="i3" identifierref="unit_1"><title>Unit 1</title></item>
+                    </item>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource identifier="r1" type="webcontent" href="intro.html"/>
+              </resources>
+              <manifest identifier="unit_1">
+                <organizations>
+                  <organization identifier="unit_org">
+                    <item identifier="unit_root">
+                      <item identifier="u1" identifierref="r1"><title>Unit page</title></item>
+                    </item>
+                  </organization>
+                </organizations>
+                <resources>
+                  <resource identifier="r1" type="webcontent" href="unit_1/page.html"/>
+                </resources>
+              </manifest>
+            </manifest>
+        "#.as_bytes());
+        assert_eq!(manifest.organizations.len(), 2);
+        let by_week = &manifest.organizations[0];
+        assert_eq!(by_week.identifier, "by_week");
+        assert_eq!(by_week.structure.as_deref(), Some("rooted-hierarchy"));
+        assert_eq!(by_week.title.as_deref(), Some("By week"));
+        let outline: Vec<(usize, &str)> =
+            by_week.all_items().iter().map(|&(depth, item)| (depth, item.title.as_str())).collect();
+        assert_eq!(outline, vec![(0, ""), (1, "Week 1"), (2, "Intro")]);
+        assert_eq!(manifest.default_organization.as_deref(), Some("by_topic"));
+        assert_eq!(manifest.modules.len(), 1);
+        assert_eq!(manifest.modules[0].title, "Topic 1");
+        assert_eq!(manifest.modules[0].items.len(), 2);
+
+        assert_eq!(manifest.resources.len(), 1);
+        assert_eq!(manifest.sub_manifests.len(), 1);
+        let unit = &manifest.sub_manifests[0];
+        assert_eq!(unit.identifier, "unit_1");
+        assert_eq!(unit.organizations[0].items[0].items[0].title, "Unit page");
+        assert_eq!(manifest.find_resource("r1").unwrap().href.as_deref(), Some("intro.html"));
+        assert_eq!(manifest.find_resource("unit_1/r1").unwrap().href.as_deref(), Some("unit_1/page.html"));
+        assert!(manifest.find_resource("unit_2/r1").is_none());
+    }
+
+    #[test]
+    fn falls_back_to_the_first_organization() {
+        let manifest = parse(r#"
+            <manifest identifier="course" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+              <organizations default="removed">
+                <organization identifier="by_week">
+                  <item identifier="root">
+                    <item identifier="week_1">
+                      <title>Week 1</title>
+                      <item identifier="i1" identifierref="r1"><title>Intro</title></item>
+                    </item>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource identifier="r1" type="webcontent" href="intro.html"/>
+              </resources>
+            </manifest>
+        "#.as_bytes());
+        assert_eq!(manifest.modules.len(), 1);
+        assert_eq!(manifest.modules[0].title, "Week 1");
+        assert_eq!(manifest.modules[0].items[0].title, "Intro");
+    }
+
+    #[test]
+    fn resolves_hrefs_against_xml_base() {
+        let manifest = parse(r#"
+            <manifest identifier="m1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1" xml:base="course/">
+              <resources xml:base="content">
+                <resource identifier="r1" type="webcontent" href="intro.html" xml:base="week_1/">
+                  <file href="intro.html"/>
+                  <file href="..\shared\Example%20File.jpg"/>
+                </resource>
+                <resource identifier="r2" type="webcontent" href="http://example.com/a%20page"/>
+              </resources>
+              <manifest identifier="sub" xml:base="unit_1/">
+                <resources>
+                  <resource identifier="r1" type="webcontent" href="page.html"/>
+                </resources>
+              </manifest>
+            </manifest>
+        "#.as_bytes());
+        let intro = manifest.find_resource("r1").unwrap();
+        assert_eq!(intro.href.as_deref(), Some("course/content/week_1/intro.html"));
+        assert_eq!(intro.files, vec!["course/content/week_1/intro.html", "course/content/shared/Example File.jpg"]);
+        assert_eq!(manifest.find_resource("r2").unwrap().href.as_deref(), Some("http://example.com/a%20page"));
+        assert_eq!(manifest.find_resource("sub/r1").unwrap().href.as_deref(), Some("course/unit_1/page.html"));
+    }
+
+    fn multilingual_xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <manifest identifier="i3ebf4e9fe673c98a2e10715ec293b6bf" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1" xmlns:lomimscc="http://ltsc.ieee.org/xsd/imsccv1p1/LOM/manifest">
+          <metadata>
+            <schema>IMS Common Cartridge</schema>
+            <schemaversion>1.1.0</schemaversion>
+            <lomimscc:lom>
+              <lomimscc:general>
+                <lomimscc:title>
+                  <lomimscc:string language="en">A Course</lomimscc:string>
+                  <lomimscc:string language="fr-CA">Un cours</lomimscc:string>
+                </lomimscc:title>
+                <lomimscc:description>
+                  <lomimscc:string language="en">test this course</lomimscc:string>
+                  <lomimscc:string language="fr">testez ce cours</lomimscc:string>
+                </lomimscc:description>
+              </lomimscc:general>
+              <lomimscc:rights>
+                <lomimscc:description>
+                  <lomimscc:string language="en">Private</lomimscc:string>
+                  <lomimscc:string language="fr">Privé</lomimscc:string>
+                </lomimscc:description>
+              </lomimscc:rights>
+            </lomimscc:lom>
+          </metadata>
+          <organizations/>
+          <resources/>
+        </manifest>
+      "#
     }
 
     fn xml_string<'a>() -> &'a str {
@@ -54,6 +371,9 @@ mod tests {
                 <lomimscc:description>
                   <lomimscc:string>test this course</lomimscc:string>
                 </lomimscc:description>
+                <lomimscc:keyword>
+                  <lomimscc:string>testing</lomimscc:string>
+                </lomimscc:keyword>
               </lomimscc:general>
               <lomimscc:lifeCycle>
                 <lomimscc:contribute>
@@ -83,6 +403,9 @@ mod tests {
                 </item>
                 <item identifier="i1ca3ec3f790e6285369f267c1be1a022">
                   <title>Module 1</title>
+                  <item identifier="i1dc6abe2ea3d5a4bde292af526bf7aad">
+                    <title>Week 1</title>
+                  </item>
                   <item identifier="i6760f3c4e14f35246d3c1b0cdab71787" identifierref="i86ed42b01897fba5a7126d18558fd7a8">
                     <title>Assignment 1</title>
                   </item>
@@ -113,6 +436,21 @@ mod tests {
               <file href="wiki_content/front-page.html"/>
             </resource>
             <resource identifier="iadc3bb46492a88f5dec93121538151df" type="webcontent" href="wiki_content/page-hidden-from-students.html">
+              <metadata>
+                <lom:lom>
+                  <lom:general>
+                    <lom:title>
+                      <lom:string>Teacher notes</lom:string>
+                    </lom:title>
+                  </lom:general>
+                  <lom:educational>
+                    <lom:intendedEndUserRole>
+                      <lom:source>IMSGLC_CC_Rolesv1p1</lom:source>
+                      <lom:value>Instructor</lom:value>
+                    </lom:intendedEndUserRole>
+                  </lom:educational>
+                </lom:lom>
+              </metadata>
               <file href="wiki_content/page-hidden-from-students.html"/>
             </resource>
             <resource identifier="i86ed42b01897fba5a7126d18558fd7a8" type="associatedcontent/imscc_xmlv1p1/learning-application-resource" href="i86ed42b01897fba5a7126d18558fd7a8/assignment-1.html">
@@ -0,0 +1,50 @@
+use common::{MediaTrack, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Collects each `<track>` with the `<media>` it belongs to.
+pub struct MediaTracksHandler {
+    pub tracks: Vec<MediaTrack>,
+    pub stack: Vec<Node>,
+}
+
+impl MediaTracksHandler {
+    pub fn new() -> MediaTracksHandler {
+        MediaTracksHandler {
+            tracks: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<MediaTrack> {
+        self.tracks
+    }
+}
+
+impl ParseHandler for MediaTracksHandler {
+    fn enter(&mut self, node: Node) {
+        if self.stack.len() == 2 && self.stack[1].has_name("media") && node.has_name("track") {
+            let mut track = MediaTrack::new(self.stack[1].find("identifierref"));
+            if let Some(kind) = node.find("kind") {
+                track.kind = kind;
+            }
+            track.locale = node.find("locale");
+            track.identifier_ref = node.find("identifierref");
+            self.tracks.push(track);
+        }
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        if self.stack.len() != 3 || !self.stack[2].has_name("track") {
+            return;
+        }
+        if let Some(track) = self.tracks.last_mut() {
+            track.content.push_str(&chars);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+mod handler;
+
+use common::MediaTrack;
+use std::io::Read;
+use summarize::media_tracks::handler::MediaTracksHandler;
+use summarize::utils::handle_parse;
+
+pub const MEDIA_TRACKS_PATH: &str = "course_settings/media_tracks.xml";
+
+/// Tracks exported as files keep an empty `content` until the file is read.
+pub fn parse<R: Read>(media_tracks: R) -> Vec<MediaTrack> {
+    let mut handler = MediaTracksHandler::new();
+    handle_parse(media_tracks, &mut handler);
+    handler.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_media_tracks() {
+        let tracks = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <media_tracks xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <media identifierref="i3d6882ab33aa3128cc2bfd7974e33351">
+                <track kind="captions" locale="en">1
+00:00:01,000 --&gt; 00:00:02,000
+Hello
+</track>
+                <track locale="es" identifierref="i0c1a3e5f7b9d2e4f6a8c0e2b4d6f8a0c"/>
+              </media>
+            </media_tracks>
+        "#.as_bytes());
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].media_identifier_ref, "i3d6882ab33aa3128cc2bfd7974e33351");
+        assert_eq!(tracks[0].kind, "captions");
+        assert_eq!(tracks[0].locale, Some("en".to_string()));
+        assert_eq!(tracks[0].to_vtt(), "WEBVTT\n\n00:00:01.000 --> 00:00:02.000\nHello\n");
+        assert_eq!(tracks[1].kind, "subtitles");
+        assert_eq!(tracks[1].identifier_ref, Some("i0c1a3e5f7b9d2e4f6a8c0e2b4d6f8a0c".to_string()));
+        assert_eq!(tracks[1].content, "");
+    }
+}
@@ -1,12 +1,226 @@
+pub mod assignment_groups;
+pub mod assignments;
+pub mod blackboard;
+pub mod course_settings;
+pub mod events;
+pub mod exporter;
+pub mod files_meta;
+pub mod grading_standards;
+pub mod json;
+pub mod locate;
+pub mod lom;
 pub mod manifest;
+pub mod media_tracks;
+pub mod module_meta;
+pub mod namespaces;
+pub mod outcomes;
+pub mod question_banks;
+pub mod rubrics;
 pub mod utils;
+pub mod weblinks;
 
-use zip::{ZipArchive};
-use std::io::{Read, Seek, Result};
-use common::Summary;
+use source::CartridgeSource;
+use std::io::{Read, Result};
+use common::{crc32_of, normalize_entry_name, EntryNames, Exporter, FileEntry, ItemType, Summary, FILES_ROOT};
+use std::collections::HashMap;
 
-pub fn summarize<R: Read + Seek>(mut archive: ZipArchive<R>) -> Result<Summary> {
-    let manifest = manifest::parse(try!(archive.by_name("imsmanifest.xml")));
-    let summary = Summary::new(manifest);
+pub fn summarize<S: CartridgeSource>(source: &mut S) -> Result<Summary> {
+    let names = source.entries()?;
+    let manifest_path = locate::manifest_path(&names)?;
+    let root = locate::root(&manifest_path);
+    let entries = EntryNames::within(root, &names);
+    let paths: Vec<String> = names.iter().filter_map(|name| name.strip_prefix(root)).map(String::from).collect();
+    let mut manifest_xml = Vec::new();
+    source.open(&manifest_path)?.read_to_end(&mut manifest_xml)?;
+    let exporter = exporter::detect(&String::from_utf8_lossy(&manifest_xml), &paths);
+    let mut manifest = manifest::parse(&manifest_xml);
+    if let Some(file) = open(source, &entries, module_meta::MODULE_META_PATH) {
+        module_meta::apply(&module_meta::parse(file), &mut manifest.modules);
+    }
+    if exporter == Exporter::Blackboard {
+        let mut contents = HashMap::new();
+        for resource in &manifest.resources {
+            if let Some(file) = resource.extension_file.as_ref().and_then(|dat| open(source, &entries, dat)) {
+                contents.insert(resource.identifier.clone(), blackboard::parse(file));
+            }
+        }
+        blackboard::apply(&contents, &mut manifest);
+    }
+    let mut links = HashMap::new();
+    for resource in manifest.resources.iter().filter(|resource| resource.item_type == ItemType::WebLink) {
+        if let Some(file) = resource.main_file().and_then(|href| open(source, &entries, href)) {
+            links.insert(resource.identifier.clone(), weblinks::parse(file));
+        }
+    }
+    for item in manifest.modules.iter_mut().flat_map(|module| module.items.iter_mut()) {
+        if item.url.is_none() {
+            item.url = links.get(&item.identifier_ref).and_then(|link| link.url.clone());
+        }
+    }
+    let mut summary = Summary::new(manifest);
+    summary.exporter = exporter;
+    if let Some(file) = open(source, &entries, course_settings::COURSE_SETTINGS_PATH) {
+        summary.course_settings = Some(course_settings::parse(file));
+    }
+    if let Some(file) = open(source, &entries, assignment_groups::ASSIGNMENT_GROUPS_PATH) {
+        summary.assignment_groups = assignment_groups::parse(file);
+    }
+    if let Some(file) = open(source, &entries, grading_standards::GRADING_STANDARDS_PATH) {
+        summary.grading_standards = grading_standards::parse(file);
+    }
+    if let Some(file) = open(source, &entries, rubrics::RUBRICS_PATH) {
+        summary.rubrics = rubrics::parse(file);
+    }
+    if let Some(file) = open(source, &entries, events::EVENTS_PATH) {
+        summary.events = events::parse(file);
+    }
+    if let Some(file) = open(source, &entries, outcomes::OUTCOMES_PATH) {
+        summary.outcomes = outcomes::parse(file);
+    }
+    let syllabus_href = summary.syllabus_resource().and_then(|resource| resource.href.clone());
+    if let Some(href) = syllabus_href {
+        if let Some(file) = open(source, &entries, &href) {
+            summary.syllabus_html = Some(read_text(file)?);
+        }
+    }
+    if let Some(file) = open(source, &entries, media_tracks::MEDIA_TRACKS_PATH) {
+        summary.media_tracks = media_tracks::parse(file);
+    }
+    for track in &mut summary.media_tracks {
+        let href = match track.identifier_ref {
+            Some(ref identifier) if track.content.is_empty() => {
+                summary.resources
+                    .iter()
+                    .find(|resource| &resource.identifier == identifier)
+                    .and_then(|resource| resource.href.clone())
+            }
+            _ => None,
+        };
+        if let Some(href) = href {
+            if let Some(file) = open(source, &entries, &href) {
+                track.content = read_text(file)?;
+            }
+        }
+    }
+    let mut qti_files = Vec::new();
+    for name in &names {
+        let path = match name.strip_prefix(root) {
+            Some(path) => path,
+            None => continue,
+        };
+        if path.starts_with(FILES_ROOT) && !path.ends_with('/') {
+            let metadata = source.metadata(name)?;
+            summary.files.insert(FileEntry {
+                size: metadata.size,
+                compressed_size: metadata.compressed_size,
+                crc32: crc32_of(source.open(name)?)?,
+                ..FileEntry::new(&normalize_entry_name(path))
+            });
+        } else if assignments::is_settings_path(path) {
+            summary.assignments.extend(assignments::parse(source.open(name)?));
+        } else if question_banks::is_qti_path(path) {
+            qti_files.push(question_banks::parse(source.open(name)?));
+        }
+    }
+    summary.question_banks = question_banks::collect(qti_files);
+    let files_meta = match open(source, &entries, files_meta::FILES_META_PATH) {
+        Some(file) => files_meta::parse(file),
+        None => files_meta::FilesMeta::default(),
+    };
+    files_meta::apply(&files_meta, &summary.resources, &mut summary.files);
     Ok(summary)
 }
+
+/// Opens the entry an `href` from the manifest refers to.
+fn open<'a, S: CartridgeSource>(source: &'a mut S, entries: &EntryNames, href: &str) -> Option<Box<dyn Read + 'a>> {
+    entries.find(href).and_then(move |name| source.open(name).ok())
+}
+
+/// Reads a text file whatever its encoding. Caption files and syllabi in
+/// particular are often Latin-1, and one of them shouldn't fail the whole
+/// cartridge.
+fn read_text<R: Read>(mut file: R) -> Result<String> {
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)?;
+    Ok(String::from_utf8_lossy(&contents).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Exporter;
+    use source::MemorySource;
+    use super::summarize;
+
+    fn manifest(resources: &str) -> String {
+        format!(r#"
+            <manifest identifier="m1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+              <organizations/>
+              <resources>{}</resources>
+            </manifest>
+        "#, resources)
+    }
+
+    #[test]
+    fn reads_sections_of_unbranded_moodle_cartridges() {
+        let mut source = MemorySource::new();
+        source.insert("imsmanifest.xml", r#"
+            <manifest identifier="M_1" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+              <organizations>
+                <organization identifier="org_1" structure="rooted-hierarchy">
+                  <item identifier="section_0">
+                    <title>General</title>
+                    <item identifier="item_1" identifierref="res_1"><title>Welcome</title></item>
+                  </item>
+                </organization>
+              </organizations>
+              <resources>
+                <resource identifier="res_1" type="webcontent" href="welcome.html"/>
+              </resources>
+            </manifest>
+        "#);
+        let summary = summarize(&mut source).unwrap();
+        assert_eq!(summary.exporter, Exporter::Unknown);
+        assert_eq!(summary.modules.len(), 1);
+        assert_eq!(summary.modules[0].title, "General");
+        assert_eq!(summary.modules[0].items[0].title, "Welcome");
+    }
+
+    #[test]
+    fn lists_files_with_hashes_in_their_names() {
+        let mut source = MemorySource::new();
+        source.insert("imsmanifest.xml", manifest(""));
+        source.insert("web_resources/Week #1/notes.pdf", vec![0u8; 4]);
+        let summary = summarize(&mut source).unwrap();
+        assert_eq!(summary.files.file("web_resources/Week #1/notes.pdf").unwrap().size, 4);
+    }
+
+    #[test]
+    fn reads_latin_1_captions() {
+        let mut source = MemorySource::new();
+        source.insert("imsmanifest.xml", manifest(r#"
+            <resource identifier="video" type="webcontent" href="web_resources/lecture.mp4"/>
+            <resource identifier="captions" type="webcontent" href="web_resources/lecture.fr.srt"/>
+        "#));
+        source.insert("course_settings/media_tracks.xml", r#"
+            <media_tracks xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+              <media identifierref="video"><track locale="fr" identifierref="captions"/></media>
+            </media_tracks>
+        "#);
+        source.insert("web_resources/lecture.mp4", vec![0u8; 4]);
+        source.insert("web_resources/lecture.fr.srt", b"1\n00:00:01,000 --> 00:00:02,000\nD\xe9j\xe0 vu\n".to_vec());
+        let summary = summarize(&mut source).unwrap();
+        assert_eq!(summary.media_tracks[0].content, "1\n00:00:01,000 --> 00:00:02,000\nD\u{fffd}j\u{fffd} vu\n");
+    }
+
+    #[test]
+    fn reads_latin_1_syllabi() {
+        let mut source = MemorySource::new();
+        source.insert("imsmanifest.xml", manifest(r#"
+            <resource identifier="syllabus" type="associatedcontent/imscc_xmlv1p1/learning-application-resource"
+                      href="course_settings/syllabus.html" intendeduse="syllabus"/>
+        "#));
+        source.insert("course_settings/syllabus.html", b"<html><body><p>R\xe9sum\xe9</p></body></html>".to_vec());
+        let summary = summarize(&mut source).unwrap();
+        assert_eq!(summary.syllabus().unwrap().text, "R\u{fffd}sum\u{fffd}");
+    }
+}
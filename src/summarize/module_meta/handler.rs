@@ -0,0 +1,121 @@
+use common::{CompletionRequirement, CompletionRequirementType, ModulePrerequisite, ParseHandler};
+use summarize::module_meta::{ModuleItemMeta, ModuleMeta};
+use summarize::utils::{parse_flag, Node};
+use xml::name::OwnedName;
+
+pub struct ModuleMetaHandler {
+    pub modules: Vec<ModuleMeta>,
+    pub stack: Vec<Node>,
+}
+
+impl ModuleMetaHandler {
+    pub fn new() -> ModuleMetaHandler {
+        ModuleMetaHandler {
+            modules: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<ModuleMeta> {
+        self.modules
+    }
+
+    fn new_prerequisite(&mut self, node: &Node) {
+        if let Some(module) = self.modules.last_mut() {
+            module.prerequisites.push(ModulePrerequisite {
+                prerequisite_type: node.find("type").unwrap_or_default(),
+                title: "".to_string(),
+                identifier_ref: "".to_string(),
+            });
+        }
+    }
+
+    fn new_completion_requirement(&mut self, node: &Node) {
+        if let Some(module) = self.modules.last_mut() {
+            let type_string = node.find("type").unwrap_or_default();
+            module.completion_requirements.push(CompletionRequirement {
+                requirement_type: CompletionRequirementType::new(&type_string),
+                identifier_ref: "".to_string(),
+            });
+        }
+    }
+}
+
+impl ParseHandler for ModuleMetaHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        match Node::path(&self.stack).as_slice() {
+            ["modules", "module"] => self.modules.push(ModuleMeta::new(node.find("identifier"))),
+            ["modules", "module", "items", "item"] => {
+                if let Some(module) = self.modules.last_mut() {
+                    module.items.push(ModuleItemMeta::new(node.find("identifier")));
+                }
+            }
+            ["modules", "module", "prerequisites", "prerequisite"] => self.new_prerequisite(&node),
+            ["modules", "module", "completionRequirements", "completionRequirement"] => {
+                self.new_completion_requirement(&node)
+            }
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let value = chars.trim().to_string();
+        let path = Node::path(&self.stack);
+        let module = match self.modules.last_mut() {
+            Some(module) => module,
+            None => return,
+        };
+        match path.as_slice() {
+            ["modules", "module", "workflow_state"] => module.workflow_state = Some(value),
+            ["modules", "module", "position"] => module.position = value.parse().ok(),
+            ["modules", "module", "locked"] => module.locked = parse_flag(&value),
+            ["modules", "module", "unlock_at"] => module.unlock_at = Some(value),
+            ["modules", "module", "require_sequential_progress"] => {
+                module.require_sequential_progress = parse_flag(&value)
+            }
+            ["modules", "module", "prerequisites", "prerequisite", field] => {
+                if let Some(prerequisite) = module.prerequisites.last_mut() {
+                    match *field {
+                        "title" => prerequisite.title = value,
+                        "identifierref" => prerequisite.identifier_ref = value,
+                        _ => {}
+                    }
+                }
+            }
+            ["modules", "module", "completionRequirements", "completionRequirement", field] => {
+                if let Some(requirement) = module.completion_requirements.last_mut() {
+                    match *field {
+                        "identifierref" => requirement.identifier_ref = value,
+                        "min_score" => {
+                            requirement.requirement_type = CompletionRequirementType::MinScore {
+                                min_score: value.parse().unwrap_or(0.0),
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ["modules", "module", "items", "item", field] => {
+                if let Some(item) = module.items.last_mut() {
+                    match *field {
+                        "content_type" => item.content_type = Some(value),
+                        "workflow_state" => item.workflow_state = Some(value),
+                        "title" => item.title = value,
+                        "identifierref" => item.identifier_ref = Some(value),
+                        "url" => item.url = Some(value),
+                        "position" => item.position = value.parse().ok(),
+                        "new_tab" => item.new_tab = parse_flag(&value),
+                        "indent" => item.indent = value.parse().unwrap_or(0),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
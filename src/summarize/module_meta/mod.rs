@@ -0,0 +1,275 @@
+mod handler;
+
+use common::{CompletionRequirement, ItemType, Module, ModuleItem, ModulePrerequisite};
+use std::io::Read;
+use summarize::module_meta::handler::ModuleMetaHandler;
+use summarize::utils::handle_parse;
+
+pub const MODULE_META_PATH: &str = "course_settings/module_meta.xml";
+
+/// A module as described by Canvas' `course_settings/module_meta.xml`.
+#[derive(Debug)]
+pub struct ModuleMeta {
+    pub identifier: String,
+    pub workflow_state: Option<String>,
+    pub position: Option<u32>,
+    pub locked: bool,
+    pub unlock_at: Option<String>,
+    pub require_sequential_progress: bool,
+    pub prerequisites: Vec<ModulePrerequisite>,
+    pub completion_requirements: Vec<CompletionRequirement>,
+    pub items: Vec<ModuleItemMeta>,
+}
+
+impl ModuleMeta {
+    pub fn new(identifier: Option<String>) -> ModuleMeta {
+        ModuleMeta {
+            identifier: identifier.unwrap_or_default(),
+            workflow_state: None,
+            position: None,
+            locked: false,
+            unlock_at: None,
+            require_sequential_progress: false,
+            prerequisites: Vec::new(),
+            completion_requirements: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ModuleItemMeta {
+    pub identifier: String,
+    pub content_type: Option<String>,
+    pub workflow_state: Option<String>,
+    pub title: String,
+    pub identifier_ref: Option<String>,
+    pub url: Option<String>,
+    pub position: Option<u32>,
+    pub new_tab: bool,
+    pub indent: u32,
+}
+
+impl ModuleItemMeta {
+    pub fn new(identifier: Option<String>) -> ModuleItemMeta {
+        ModuleItemMeta {
+            identifier: identifier.unwrap_or_default(),
+            content_type: None,
+            workflow_state: None,
+            title: "".to_string(),
+            identifier_ref: None,
+            url: None,
+            position: None,
+            new_tab: false,
+            indent: 0,
+        }
+    }
+}
+
+pub fn parse<R: Read>(module_meta: R) -> Vec<ModuleMeta> {
+    let mut handler = ModuleMetaHandler::new();
+    handle_parse(module_meta, &mut handler);
+    handler.finalize()
+}
+
+/// Copies Canvas module state onto the manifest's modules. Modules are
+/// joined on identifier; items on identifier, falling back to the manifest
+/// item's `identifierref`, which is what Canvas uses for external tools.
+/// Items are put in Canvas' order when every one of them has a position.
+pub fn apply(metas: &[ModuleMeta], modules: &mut [Module]) {
+    for module in modules.iter_mut() {
+        let meta = match metas.iter().find(|meta| meta.identifier == module.identifier) {
+            Some(meta) => meta,
+            None => continue,
+        };
+        module.published = is_published(&meta.workflow_state);
+        module.workflow_state = meta.workflow_state.clone();
+        module.position = meta.position;
+        module.locked = meta.locked;
+        module.unlock_at = meta.unlock_at.clone();
+        module.require_sequential_progress = meta.require_sequential_progress;
+        module.prerequisites = meta.prerequisites.clone();
+        module.completion_requirements = meta.completion_requirements.clone();
+        for item in module.items.iter_mut() {
+            let item_meta = meta.items
+                .iter()
+                .find(|item_meta| item_meta.identifier == item.identifier)
+                .or_else(|| meta.items.iter().find(|item_meta| item_meta.identifier == item.identifier_ref));
+            if let Some(item_meta) = item_meta {
+                apply_item(item_meta, item);
+            }
+        }
+        if module.items.iter().all(|item| item.position.is_some()) {
+            module.items.sort_by_key(|item| item.position);
+        }
+    }
+}
+
+fn apply_item(meta: &ModuleItemMeta, item: &mut ModuleItem) {
+    item.published = is_published(&meta.workflow_state);
+    item.content_type = meta.content_type.clone();
+    item.position = meta.position;
+    item.indent = meta.indent;
+    item.new_tab = meta.new_tab;
+    item.url = meta.url.clone();
+    match meta.content_type.as_deref() {
+        Some("ContextModuleSubHeader") => item.item_type = ItemType::SubHeader,
+        Some("ExternalUrl") => item.item_type = ItemType::ExternalUrl,
+        Some("ContextExternalTool") => item.item_type = ItemType::ExternalTool,
+        _ => {}
+    }
+}
+
+fn is_published(workflow_state: &Option<String>) -> bool {
+    !matches!(workflow_state.as_deref(), Some("unpublished") | Some("deleted"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, parse};
+    use common::{CompletionRequirementType, ItemType, Module, ModuleItem};
+
+    #[test]
+    fn parses_workflow_states() {
+        let metas = parse(xml_string().as_bytes());
+        assert_eq!(metas.len(), 2);
+        assert_eq!(metas[0].identifier, "id7d0efe702450aa5e9e1664dfeaa94ee");
+        assert_eq!(metas[0].items[0].workflow_state, Some("unpublished".to_string()));
+        assert_eq!(metas[1].workflow_state, Some("unpublished".to_string()));
+    }
+
+    #[test]
+    fn parses_module_settings() {
+        let metas = parse(xml_string().as_bytes());
+        let module = &metas[1];
+        assert_eq!(module.position, Some(2));
+        assert!(module.locked);
+        assert!(module.require_sequential_progress);
+        assert_eq!(module.unlock_at, Some("2016-04-01T06:00:00".to_string()));
+        assert_eq!(module.prerequisites.len(), 1);
+        assert_eq!(module.prerequisites[0].prerequisite_type, "context_module");
+        assert_eq!(module.prerequisites[0].identifier_ref, "id7d0efe702450aa5e9e1664dfeaa94ee");
+        assert_eq!(module.completion_requirements.len(), 2);
+        assert_eq!(module.completion_requirements[0].requirement_type,
+                   CompletionRequirementType::MinScore { min_score: 8.0 });
+        assert_eq!(module.completion_requirements[1].requirement_type, CompletionRequirementType::MustView);
+        assert_eq!(module.completion_requirements[1].identifier_ref, "ib757ee275750d12defb5aff861f6cbb5");
+    }
+
+    #[test]
+    fn parses_item_settings() {
+        let metas = parse(xml_string().as_bytes());
+        let item = &metas[0].items[0];
+        assert_eq!(item.content_type, Some("ContextExternalTool".to_string()));
+        assert_eq!(item.url, Some("https://lor-staging.instructure.com/api/v1/debug/request/body".to_string()));
+        assert!(item.new_tab);
+        assert_eq!(item.indent, 1);
+        assert_eq!(item.position, Some(1));
+    }
+
+    #[test]
+    fn applies_published_state() {
+        let metas = parse(xml_string().as_bytes());
+        let item = ModuleItem::new("ib3ace830fa129c9d1c24702eebf3ebb4".to_string(),
+                                   "ib81691c293cfcd8474622cfd8c0047f5".to_string(),
+                                   "lor debug".to_string(),
+                                   ItemType::NoType);
+        let mut modules = vec![
+            Module::new("id7d0efe702450aa5e9e1664dfeaa94ee".to_string(), "LOR Testing".to_string(), vec![item]),
+            Module::new("i1ca3ec3f790e6285369f267c1be1a022".to_string(), "Module 1".to_string(), Vec::new()),
+        ];
+        apply(&metas, &mut modules);
+        assert!(modules[0].published);
+        assert!(!modules[0].items[0].published);
+        assert!(!modules[1].published);
+        assert!(modules[1].locked);
+        assert_eq!(modules[1].completion_requirements.len(), 2);
+        assert_eq!(modules[0].items[0].content_type, Some("ContextExternalTool".to_string()));
+        assert!(modules[0].items[0].new_tab);
+        assert_eq!(modules[0].items[0].item_type, ItemType::ExternalTool);
+    }
+
+    #[test]
+    fn applies_item_types_and_order() {
+        let metas = parse(xml_string().as_bytes());
+        let header = ModuleItem::new("i1dc6abe2ea3d5a4bde292af526bf7aad".to_string(),
+                                     "".to_string(),
+                                     "Text Header Example".to_string(),
+                                     ItemType::SubHeader);
+        let link = ModuleItem::new("ib757ee275750d12defb5aff861f6cbb5".to_string(),
+                                   "idcea97b5504b66c25b02bd537dc4c748".to_string(),
+                                   "Google".to_string(),
+                                   ItemType::NoType);
+        let mut modules = vec![
+            Module::new("i1ca3ec3f790e6285369f267c1be1a022".to_string(), "Module 1".to_string(), vec![link, header]),
+        ];
+        apply(&metas, &mut modules);
+        let items = &modules[0].items;
+        assert_eq!(items[0].title, "Text Header Example");
+        assert_eq!(items[0].item_type, ItemType::SubHeader);
+        assert_eq!(items[1].item_type, ItemType::ExternalUrl);
+        assert_eq!(items[1].url, Some("http://www.google.com".to_string()));
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <modules xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+          <module identifier="id7d0efe702450aa5e9e1664dfeaa94ee">
+            <title>LOR Testing</title>
+            <workflow_state>active</workflow_state>
+            <items>
+              <item identifier="ib81691c293cfcd8474622cfd8c0047f5">
+                <content_type>ContextExternalTool</content_type>
+                <workflow_state>unpublished</workflow_state>
+                <title>lor debug</title>
+                <identifierref>i46df8266805f7e1538d0bfdbaa6d65b6</identifierref>
+                <url>https://lor-staging.instructure.com/api/v1/debug/request/body</url>
+                <position>1</position>
+                <new_tab>true</new_tab>
+                <indent>1</indent>
+              </item>
+            </items>
+          </module>
+          <module identifier="i1ca3ec3f790e6285369f267c1be1a022">
+            <title>Module 1</title>
+            <workflow_state>unpublished</workflow_state>
+            <position>2</position>
+            <require_sequential_progress>true</require_sequential_progress>
+            <locked>true</locked>
+            <unlock_at>2016-04-01T06:00:00</unlock_at>
+            <prerequisites>
+              <prerequisite type="context_module">
+                <title>LOR Testing</title>
+                <identifierref>id7d0efe702450aa5e9e1664dfeaa94ee</identifierref>
+              </prerequisite>
+            </prerequisites>
+            <items>
+              <item identifier="i1dc6abe2ea3d5a4bde292af526bf7aad">
+                <content_type>ContextModuleSubHeader</content_type>
+                <workflow_state>active</workflow_state>
+                <title>Text Header Example</title>
+                <position>1</position>
+              </item>
+              <item identifier="ib757ee275750d12defb5aff861f6cbb5">
+                <content_type>ExternalUrl</content_type>
+                <workflow_state>active</workflow_state>
+                <title>Google</title>
+                <url>http://www.google.com</url>
+                <position>2</position>
+              </item>
+            </items>
+            <completionRequirements>
+              <completionRequirement type="min_score">
+                <min_score>8</min_score>
+                <identifierref>ic1c14d8fd18566c42573fb2e3d50b480</identifierref>
+              </completionRequirement>
+              <completionRequirement type="must_view">
+                <identifierref>ib757ee275750d12defb5aff861f6cbb5</identifierref>
+              </completionRequirement>
+            </completionRequirements>
+          </module>
+        </modules>
+      "#
+    }
+}
@@ -0,0 +1,132 @@
+use regex::Regex;
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// The namespaces the manifest parser reads. Each covers the URIs of every
+/// Common Cartridge version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Namespace {
+    /// IMS Content Packaging: `manifest`, `organization`, `item`, `resource`.
+    ContentPackaging,
+    /// LOM for the cartridge, `lomimscc:` by convention.
+    ManifestLom,
+    /// LOM for a resource, `lom:` by convention.
+    ResourceLom,
+    /// Blackboard's extensions, `bb:` by convention.
+    Blackboard,
+}
+
+impl Namespace {
+    pub fn contains(&self, uri: &str) -> bool {
+        lazy_static! {
+            static ref CONTENT_PACKAGING_R: Regex =
+                Regex::new(r"^http://www\.imsglobal\.org/xsd/(imscc/|imsccv1p\d/)?imscp_v1p[12]$").unwrap();
+            static ref MANIFEST_LOM_R: Regex =
+                Regex::new(r"^http://ltsc\.ieee\.org/xsd/(imscc/LOM|imsccv1p\d/LOM/manifest|LOM)$").unwrap();
+            static ref RESOURCE_LOM_R: Regex =
+                Regex::new(r"^http://ltsc\.ieee\.org/xsd/(imscc/LOM|imsccv1p\d/LOM/resource|LOM)$").unwrap();
+            static ref BLACKBOARD_R: Regex =
+                Regex::new(r"^https?://www\.blackboard\.com/content-packaging/?$").unwrap();
+        }
+        match *self {
+            Namespace::ContentPackaging => CONTENT_PACKAGING_R.is_match(uri),
+            Namespace::ManifestLom => MANIFEST_LOM_R.is_match(uri),
+            Namespace::ResourceLom => RESOURCE_LOM_R.is_match(uri),
+            Namespace::Blackboard => BLACKBOARD_R.is_match(uri),
+        }
+    }
+
+    fn is_lom(&self) -> bool {
+        *self == Namespace::ManifestLom || *self == Namespace::ResourceLom
+    }
+}
+
+/// How element namespaces are checked. A manifest in a known namespace is
+/// read strictly. Broken exporters that leave the namespace out or
+/// misspell it get a lenient reading: elements in no namespace, in the
+/// manifest's own namespace, or in either LOM namespace for LOM, also match.
+/// Vendor extension elements never do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Matching {
+    Strict,
+    Lenient { manifest_namespace: Option<String> },
+}
+
+impl Matching {
+    pub fn for_manifest(manifest: &Node) -> Matching {
+        match manifest.name.namespace {
+            Some(ref uri) if Namespace::ContentPackaging.contains(uri) => Matching::Strict,
+            ref namespace => Matching::Lenient { manifest_namespace: namespace.clone() },
+        }
+    }
+
+    pub fn matches(&self, node: &Node, namespace: Namespace, local_name: &str) -> bool {
+        self.matches_name(&node.name, namespace, local_name)
+    }
+
+    pub fn matches_name(&self, name: &OwnedName, namespace: Namespace, local_name: &str) -> bool {
+        if name.local_name != local_name {
+            return false;
+        }
+        let uri = match name.namespace {
+            Some(ref uri) => uri,
+            None => return *self != Matching::Strict,
+        };
+        if namespace.contains(uri) {
+            return true;
+        }
+        match *self {
+            Matching::Strict => false,
+            Matching::Lenient { ref manifest_namespace } => {
+                let lom = namespace.is_lom() &&
+                          (Namespace::ManifestLom.contains(uri) || Namespace::ResourceLom.contains(uri));
+                lom || (namespace == Namespace::ContentPackaging && manifest_namespace.as_ref() == Some(uri))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Matching, Namespace};
+    use summarize::utils::Node;
+    use xml::name::OwnedName;
+
+    fn node(namespace: Option<&str>, local_name: &str) -> Node {
+        let mut name = OwnedName::local(local_name);
+        name.namespace = namespace.map(|uri| uri.to_string());
+        Node::new(name, Vec::new())
+    }
+
+    #[test]
+    fn matches_known_namespaces() {
+        for uri in &["http://www.imsglobal.org/xsd/imscc/imscp_v1p1",
+                     "http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1",
+                     "http://www.imsglobal.org/xsd/imsccv1p3/imscp_v1p1"] {
+            assert!(Namespace::ContentPackaging.contains(uri));
+        }
+        assert!(Namespace::ManifestLom.contains("http://ltsc.ieee.org/xsd/imsccv1p2/LOM/manifest"));
+        assert!(!Namespace::ManifestLom.contains("http://ltsc.ieee.org/xsd/imsccv1p2/LOM/resource"));
+        assert!(Namespace::ResourceLom.contains("http://ltsc.ieee.org/xsd/imscc/LOM"));
+        assert!(!Namespace::ContentPackaging.contains("http://www.blackboard.com/content-packaging/"));
+    }
+
+    #[test]
+    fn matches_leniently_for_broken_manifests() {
+        let cp = "http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1";
+        let vendor = "http://www.blackboard.com/content-packaging/";
+        let strict = Matching::for_manifest(&node(Some(cp), "manifest"));
+        assert_eq!(strict, Matching::Strict);
+        assert!(strict.matches(&node(Some(cp), "item"), Namespace::ContentPackaging, "item"));
+        assert!(!strict.matches(&node(None, "item"), Namespace::ContentPackaging, "item"));
+        assert!(!strict.matches(&node(Some(vendor), "title"), Namespace::ContentPackaging, "title"));
+
+        let misspelled = "http://www.imsglobal.org/xsd/imscp_v1p1/";
+        let lenient = Matching::for_manifest(&node(Some(misspelled), "manifest"));
+        assert!(lenient.matches(&node(Some(misspelled), "item"), Namespace::ContentPackaging, "item"));
+        assert!(lenient.matches(&node(None, "item"), Namespace::ContentPackaging, "item"));
+        let resource_lom = "http://ltsc.ieee.org/xsd/imsccv1p1/LOM/resource";
+        assert!(lenient.matches(&node(Some(resource_lom), "lom"), Namespace::ManifestLom, "lom"));
+        assert!(!lenient.matches(&node(Some(vendor), "title"), Namespace::ContentPackaging, "title"));
+    }
+}
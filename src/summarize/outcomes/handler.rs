@@ -0,0 +1,136 @@
+use common::{Outcome, OutcomeAlignment, OutcomeGroup, OutcomeRating, ParseHandler};
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Groups nest through `<learningOutcomes>` elements, so open groups are
+/// kept on their own stack with the file's top level at the bottom.
+pub struct OutcomesHandler {
+    pub groups: Vec<OutcomeGroup>,
+    pub outcome: Option<Outcome>,
+    pub stack: Vec<Node>,
+}
+
+impl OutcomesHandler {
+    pub fn new() -> OutcomesHandler {
+        OutcomesHandler {
+            groups: vec![OutcomeGroup::default()],
+            outcome: None,
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(mut self) -> OutcomeGroup {
+        while self.groups.len() > 1 {
+            self.close_group();
+        }
+        self.groups.pop().unwrap_or_default()
+    }
+
+    fn close_group(&mut self) {
+        if let Some(group) = self.groups.pop() {
+            if let Some(parent) = self.groups.last_mut() {
+                parent.groups.push(group);
+            }
+        }
+    }
+
+    fn receive_outcome_chars(&mut self, chars: String) {
+        let depth = self.stack.len();
+        let outcome = match self.outcome {
+            Some(ref mut outcome) => outcome,
+            None => return,
+        };
+        let field = self.stack[depth - 1].name_str();
+        let parent = self.stack[depth - 2].name_str();
+        let value = chars.trim();
+        match parent {
+            "learningOutcome" => match field {
+                "title" => outcome.title.push_str(value),
+                "description" => outcome.description.get_or_insert_with(String::new).push_str(&chars),
+                "vendor_guid" => outcome.vendor_guid = Some(value.to_string()),
+                "calculation_method" => outcome.calculation_method = Some(value.to_string()),
+                "calculation_int" => outcome.calculation_int = value.parse().ok(),
+                "points_possible" => outcome.points_possible = value.parse().ok(),
+                "mastery_points" => outcome.mastery_points = value.parse().ok(),
+                _ => {}
+            },
+            "rating" => {
+                if let Some(rating) = outcome.ratings.last_mut() {
+                    match field {
+                        "description" => rating.description.push_str(value),
+                        "points" => rating.points = value.parse().unwrap_or(0.0),
+                        _ => {}
+                    }
+                }
+            }
+            "alignment" => {
+                if let Some(alignment) = outcome.alignments.last_mut() {
+                    match field {
+                        "content_type" => alignment.content_type.push_str(value),
+                        "content_id" => alignment.content_id.push_str(value),
+                        "mastery_type" => alignment.mastery_type = Some(value.to_string()),
+                        "mastery_score" => alignment.mastery_score = value.parse().ok(),
+                        "position" => alignment.position = value.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ParseHandler for OutcomesHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        match node.name_str() {
+            "learningOutcomeGroup" => self.groups.push(OutcomeGroup::new(node.find("identifier"))),
+            "learningOutcome" => self.outcome = Some(Outcome::new(node.find("identifier"))),
+            "rating" => {
+                if let Some(ref mut outcome) = self.outcome {
+                    outcome.ratings.push(OutcomeRating::default());
+                }
+            }
+            "alignment" => {
+                if let Some(ref mut outcome) = self.outcome {
+                    outcome.alignments.push(OutcomeAlignment::default());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, name: OwnedName) {
+        self.stack.pop();
+        match name.local_name.as_str() {
+            "learningOutcomeGroup" => self.close_group(),
+            "learningOutcome" => {
+                if let (Some(outcome), Some(group)) = (self.outcome.take(), self.groups.last_mut()) {
+                    group.outcomes.push(outcome);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let depth = self.stack.len();
+        if depth < 2 {
+            return;
+        }
+        if self.outcome.is_some() {
+            return self.receive_outcome_chars(chars);
+        }
+        if !self.stack[depth - 2].has_name("learningOutcomeGroup") {
+            return;
+        }
+        if let Some(group) = self.groups.last_mut() {
+            match self.stack[depth - 1].name_str() {
+                "title" => group.title.push_str(chars.trim()),
+                "description" => group.description.get_or_insert_with(String::new).push_str(&chars),
+                "vendor_guid" => group.vendor_guid = Some(chars.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+}
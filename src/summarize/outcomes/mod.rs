@@ -0,0 +1,105 @@
+mod handler;
+
+use common::OutcomeGroup;
+use std::io::Read;
+use summarize::outcomes::handler::OutcomesHandler;
+use summarize::utils::handle_parse;
+
+pub const OUTCOMES_PATH: &str = "course_settings/learning_outcomes.xml";
+
+pub fn parse<R: Read>(outcomes: R) -> OutcomeGroup {
+    let mut handler = OutcomesHandler::new();
+    handle_parse(outcomes, &mut handler);
+    handler.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_outcome_tree() {
+        let root = parse(xml_string().as_bytes());
+        assert_eq!(root.identifier, "");
+        assert_eq!(root.outcomes.len(), 1);
+        assert_eq!(root.outcomes[0].title, "Ungrouped Outcome");
+        assert_eq!(root.groups.len(), 1);
+        let group = &root.groups[0];
+        assert_eq!(group.identifier, "i26c96a8374d7a3d78f7c874f9ee513e3");
+        assert_eq!(group.title, "Example Outcome Group");
+        assert_eq!(group.description.as_deref().map(str::trim), Some("<p>Group description</p>"));
+        assert_eq!(group.groups.len(), 1);
+        assert_eq!(group.groups[0].outcomes[0].identifier, "i3c5a9e3d2f1b0a9c8d7e6f5a4b3c2d1e");
+        let identifiers: Vec<&str> = root.all_outcomes().iter().map(|outcome| outcome.identifier.as_str()).collect();
+        assert_eq!(identifiers.len(), 3);
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        let root = parse(xml_string().as_bytes());
+        let outcome = root.find_outcome("i69aed9fc9ce7e3161c8225fa85a328b4").unwrap();
+        assert_eq!(outcome.title, "Example Outcome");
+        assert_eq!(outcome.points_possible, Some(5.0));
+        assert_eq!(outcome.mastery_points, Some(4.0));
+        assert_eq!(outcome.calculation_method, Some("decaying_average".to_string()));
+        assert_eq!(outcome.calculation_int, Some(65));
+        assert_eq!(outcome.ratings.len(), 2);
+        assert_eq!(outcome.ratings[1].description, "Does Not Meet Expectations");
+        assert_eq!(outcome.alignments.len(), 1);
+        assert_eq!(outcome.alignments[0].content_type, "Rubric");
+        assert_eq!(outcome.alignments[0].content_id, "ibff36139d637040dc9402090a3ec75f8");
+        assert_eq!(outcome.alignments[0].mastery_score, None);
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <learningOutcomes xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+          <learningOutcomeGroup identifier="i26c96a8374d7a3d78f7c874f9ee513e3">
+            <title>Example Outcome Group</title>
+            <description>&lt;p&gt;Group description&lt;/p&gt;</description>
+            <learningOutcomes>
+              <learningOutcome identifier="i69aed9fc9ce7e3161c8225fa85a328b4">
+                <title>Example Outcome</title>
+                <description>&lt;p&gt;Outcome description&lt;/p&gt;</description>
+                <calculation_method>decaying_average</calculation_method>
+                <calculation_int>65</calculation_int>
+                <alignments>
+                  <alignment>
+                    <content_type>Rubric</content_type>
+                    <content_id>ibff36139d637040dc9402090a3ec75f8</content_id>
+                    <mastery_type>explicit_mastery</mastery_type>
+                    <mastery_score/>
+                    <position>2</position>
+                  </alignment>
+                </alignments>
+                <points_possible>5</points_possible>
+                <mastery_points>4</mastery_points>
+                <ratings>
+                  <rating>
+                    <description>Perfection!</description>
+                    <points>5</points>
+                  </rating>
+                  <rating>
+                    <description>Does Not Meet Expectations</description>
+                    <points>0</points>
+                  </rating>
+                </ratings>
+              </learningOutcome>
+              <learningOutcomeGroup identifier="i0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a">
+                <title>Nested Group</title>
+                <learningOutcomes>
+                  <learningOutcome identifier="i3c5a9e3d2f1b0a9c8d7e6f5a4b3c2d1e">
+                    <title>Nested Outcome</title>
+                  </learningOutcome>
+                </learningOutcomes>
+              </learningOutcomeGroup>
+            </learningOutcomes>
+          </learningOutcomeGroup>
+          <learningOutcome identifier="i9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b">
+            <title>Ungrouped Outcome</title>
+          </learningOutcome>
+        </learningOutcomes>
+      "#
+    }
+}
@@ -0,0 +1,64 @@
+use common::{ParseHandler, QuestionBank};
+use summarize::question_banks::QtiFile;
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Reads bank titles from `<objectbank>` and the banks an `<assessment>`
+/// draws from through `<sourcebank_ref>`; questions are skipped.
+pub struct QtiHandler {
+    pub file: QtiFile,
+    pub stack: Vec<Node>,
+    field_label: String,
+}
+
+impl QtiHandler {
+    pub fn new() -> QtiHandler {
+        QtiHandler {
+            file: QtiFile::default(),
+            stack: Vec::new(),
+            field_label: String::new(),
+        }
+    }
+
+    pub fn finalize(self) -> QtiFile {
+        self.file
+    }
+}
+
+impl ParseHandler for QtiHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        match Node::path(&self.stack).as_slice() {
+            ["questestinterop", "objectbank"] => self.file.banks.push(QuestionBank {
+                identifier: node.find("ident").unwrap_or_default(),
+                ..QuestionBank::default()
+            }),
+            ["questestinterop", "assessment"] => self.file.quiz_identifier = node.find("ident"),
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let value = chars.trim().to_string();
+        match Node::path(&self.stack).as_slice() {
+            ["questestinterop", "objectbank", "qtimetadata", "qtimetadatafield", "fieldlabel"] => {
+                self.field_label = value
+            }
+            ["questestinterop", "objectbank", "qtimetadata", "qtimetadatafield", "fieldentry"]
+                if self.field_label == "bank_title" =>
+            {
+                if let Some(bank) = self.file.banks.last_mut() {
+                    bank.title = value;
+                }
+            }
+            ["questestinterop", "assessment", .., "sourcebank_ref"] if !self.file.bank_refs.contains(&value) => {
+                self.file.bank_refs.push(value)
+            }
+            _ => {}
+        }
+    }
+}
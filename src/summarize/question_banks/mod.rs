@@ -0,0 +1,103 @@
+mod handler;
+
+use common::QuestionBank;
+use std::io::Read;
+use summarize::question_banks::handler::QtiHandler;
+use summarize::utils::handle_parse;
+
+/// What one QTI file says about question banks: the banks it defines, or
+/// the quiz it defines and the banks that quiz draws from.
+#[derive(Debug, Default)]
+pub struct QtiFile {
+    pub banks: Vec<QuestionBank>,
+    pub quiz_identifier: Option<String>,
+    pub bank_refs: Vec<String>,
+}
+
+/// Canvas writes banks and its own copy of each quiz to `non_cc_assessments`.
+pub fn is_qti_path(path: &str) -> bool {
+    path.starts_with("non_cc_assessments/") && path.ends_with(".xml.qti")
+}
+
+pub fn parse<R: Read>(qti: R) -> QtiFile {
+    let mut handler = QtiHandler::new();
+    handle_parse(qti, &mut handler);
+    handler.finalize()
+}
+
+/// Merges parsed files into banks that know which quizzes use them.
+pub fn collect(files: Vec<QtiFile>) -> Vec<QuestionBank> {
+    let mut banks: Vec<QuestionBank> = files.iter().flat_map(|file| file.banks.iter().cloned()).collect();
+    for file in &files {
+        let quiz = match file.quiz_identifier {
+            Some(ref quiz) => quiz,
+            None => continue,
+        };
+        for bank_ref in &file.bank_refs {
+            let index = match banks.iter().position(|bank| &bank.identifier == bank_ref) {
+                Some(index) => index,
+                None => {
+                    banks.push(QuestionBank { identifier: bank_ref.clone(), ..QuestionBank::default() });
+                    banks.len() - 1
+                }
+            };
+            if !banks[index].quiz_identifiers.contains(quiz) {
+                banks[index].quiz_identifiers.push(quiz.clone());
+            }
+        }
+    }
+    banks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect, parse};
+
+    #[test]
+    fn links_quizzes_to_banks() {
+        let bank = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <questestinterop xmlns="http://www.imsglobal.org/xsd/ims_qtiasiv1p2">
+              <objectbank ident="i8ac513be7b4986966c1a7784dc8373c6">
+                <qtimetadata>
+                  <qtimetadatafield>
+                    <fieldlabel>bank_title</fieldlabel>
+                    <fieldentry>Unfiled Questions</fieldentry>
+                  </qtimetadatafield>
+                </qtimetadata>
+                <item ident="ia9617116a4bd14fefbae6515a4e62939" title="Question"/>
+              </objectbank>
+            </questestinterop>
+        "#.as_bytes());
+        let quiz = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <questestinterop xmlns="http://www.imsglobal.org/xsd/ims_qtiasiv1p2">
+              <assessment ident="i9b472fe6bfd6a729f056be5da05da798" title="Quiz 86">
+                <section ident="root_section">
+                  <section ident="i4b0d6d9f6b6a1e5a0f0e5d4c3b2a1f0e" title="Question Group">
+                    <selection_ordering>
+                      <selection>
+                        <sourcebank_ref>i8ac513be7b4986966c1a7784dc8373c6</sourcebank_ref>
+                        <selection_number>2</selection_number>
+                      </selection>
+                    </selection_ordering>
+                  </section>
+                  <section ident="i5c1e7e0a7c7b2f6b1a1f6e5d4c3b2a1f" title="Account Group">
+                    <selection_ordering>
+                      <selection>
+                        <sourcebank_ref>account_bank_7</sourcebank_ref>
+                      </selection>
+                    </selection_ordering>
+                  </section>
+                </section>
+              </assessment>
+            </questestinterop>
+        "#.as_bytes());
+        let banks = collect(vec![bank, quiz]);
+        assert_eq!(banks.len(), 2);
+        assert_eq!(banks[0].title, "Unfiled Questions");
+        assert_eq!(banks[0].quiz_identifiers, vec!["i9b472fe6bfd6a729f056be5da05da798"]);
+        assert_eq!(banks[1].identifier, "account_bank_7");
+        assert_eq!(banks[1].title, "");
+    }
+}
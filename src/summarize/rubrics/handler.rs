@@ -0,0 +1,94 @@
+use common::{ParseHandler, Rubric, RubricCriterion, RubricRating};
+use summarize::utils::{parse_flag, Node};
+use xml::name::OwnedName;
+
+pub struct RubricsHandler {
+    pub rubrics: Vec<Rubric>,
+    pub stack: Vec<Node>,
+}
+
+impl RubricsHandler {
+    pub fn new() -> RubricsHandler {
+        RubricsHandler {
+            rubrics: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<Rubric> {
+        self.rubrics
+    }
+}
+
+impl ParseHandler for RubricsHandler {
+    fn enter(&mut self, node: Node) {
+        self.stack.push(node.clone());
+        match Node::path(&self.stack).as_slice() {
+            ["rubrics", "rubric"] => self.rubrics.push(Rubric::new(node.find("identifier"))),
+            ["rubrics", "rubric", "criteria", "criterion"] => {
+                if let Some(rubric) = self.rubrics.last_mut() {
+                    rubric.criteria.push(RubricCriterion::default());
+                }
+            }
+            ["rubrics", "rubric", "criteria", "criterion", "ratings", "rating"] => {
+                if let Some(criterion) = self.rubrics.last_mut().and_then(|rubric| rubric.criteria.last_mut()) {
+                    criterion.ratings.push(RubricRating::default());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        let path = Node::path(&self.stack);
+        let rubric = match self.rubrics.last_mut() {
+            Some(rubric) => rubric,
+            None => return,
+        };
+        match path.as_slice() {
+            ["rubrics", "rubric", field] => match *field {
+                "title" => rubric.title.push_str(&chars),
+                "points_possible" => rubric.points_possible = chars.trim().parse().ok(),
+                "free_form_criterion_comments" => rubric.free_form_criterion_comments = parse_flag(&chars),
+                "hide_score_total" => rubric.hide_score_total = parse_flag(&chars),
+                _ => {}
+            },
+            ["rubrics", "rubric", "criteria", "criterion", field] => {
+                let criterion = match rubric.criteria.last_mut() {
+                    Some(criterion) => criterion,
+                    None => return,
+                };
+                match *field {
+                    "criterion_id" => criterion.id.push_str(chars.trim()),
+                    "description" => criterion.description.push_str(&chars),
+                    "long_description" => criterion.long_description.get_or_insert_with(String::new).push_str(&chars),
+                    "points" => criterion.points = chars.trim().parse().unwrap_or(0.0),
+                    "mastery_points" => criterion.mastery_points = chars.trim().parse().ok(),
+                    "ignore_for_scoring" => criterion.ignore_for_scoring = parse_flag(&chars),
+                    "learning_outcome_identifierref" => {
+                        criterion.learning_outcome_identifier_ref = Some(chars.trim().to_string())
+                    }
+                    _ => {}
+                }
+            }
+            ["rubrics", "rubric", "criteria", "criterion", "ratings", "rating", field] => {
+                let rating = match rubric.criteria.last_mut().and_then(|criterion| criterion.ratings.last_mut()) {
+                    Some(rating) => rating,
+                    None => return,
+                };
+                match *field {
+                    "id" => rating.id.push_str(chars.trim()),
+                    "description" => rating.description.push_str(&chars),
+                    "long_description" => rating.long_description.get_or_insert_with(String::new).push_str(&chars),
+                    "points" => rating.points = chars.trim().parse().unwrap_or(0.0),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
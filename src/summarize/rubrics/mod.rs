@@ -0,0 +1,127 @@
+mod handler;
+
+use common::Rubric;
+use std::io::Read;
+use summarize::rubrics::handler::RubricsHandler;
+use summarize::utils::handle_parse;
+
+pub const RUBRICS_PATH: &str = "course_settings/rubrics.xml";
+
+pub fn parse<R: Read>(rubrics: R) -> Vec<Rubric> {
+    let mut handler = RubricsHandler::new();
+    handle_parse(rubrics, &mut handler);
+    handler.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_rubrics() {
+        let rubrics = parse(xml_string().as_bytes());
+        assert_eq!(rubrics.len(), 1);
+        let rubric = &rubrics[0];
+        assert_eq!(rubric.identifier, "ibff36139d637040dc9402090a3ec75f8");
+        assert_eq!(rubric.title, "Example Course Rubric");
+        assert_eq!(rubric.points_possible, Some(10.0));
+        assert_eq!(rubric.criteria.len(), 2);
+        assert_eq!(rubric.criteria[0].id, "9_8244");
+        assert_eq!(rubric.criteria[0].ratings.len(), 2);
+        assert_eq!(rubric.criteria[0].ratings[1].description, "No Marks");
+        assert_eq!(rubric.criteria[0].ratings[1].points, 0.0);
+        let outcome = &rubric.criteria[1];
+        assert_eq!(outcome.mastery_points, Some(4.0));
+        assert_eq!(outcome.long_description, Some("<p>Lorem ipsum</p>".to_string()));
+        assert_eq!(outcome.learning_outcome_identifier_ref, Some("i69aed9fc9ce7e3161c8225fa85a328b4".to_string()));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = parse(xml_string().as_bytes())[0].to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "criterion_id,criterion,criterion_points,rating_id,rating,rating_points,learning_outcome");
+        assert_eq!(lines[1], "9_8244,Example Rubric Criteria,5,blank,Full Marks,5,");
+        assert_eq!(lines[3], "9_268,\"Outcome, with a comma\",5,9_5527,Perfection!,5,i69aed9fc9ce7e3161c8225fa85a328b4");
+    }
+
+    #[test]
+    fn exports_html() {
+        let html = parse(xml_string().as_bytes())[0].to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Example Course Rubric</h1>"));
+        assert!(html.contains("<div class=\"long-description\">&lt;p&gt;Lorem ipsum&lt;/p&gt;</div>"));
+        assert!(html.contains("<div>Missing &lt;or&gt; late</div>"));
+        assert!(html.contains("<div>Threshold: 4 pts</div>"));
+        assert!(html.contains("Total Points: 10"));
+    }
+
+    #[test]
+    fn escapes_html_in_long_descriptions() {
+        let xml = xml_string().replace("&lt;p&gt;Lorem ipsum&lt;/p&gt;", "&lt;script&gt;alert(1)&lt;/script&gt;");
+        let html = parse(xml.as_bytes())[0].to_html();
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<div class=\"long-description\">&lt;script&gt;alert(1)&lt;/script&gt;</div>"));
+    }
+
+    fn xml_string<'a>() -> &'a str {
+        r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <rubrics xmlns="http://canvas.instructure.com/xsd/cccv1p0">
+          <rubric identifier="ibff36139d637040dc9402090a3ec75f8">
+            <read_only>false</read_only>
+            <title>Example Course Rubric</title>
+            <points_possible>10</points_possible>
+            <hide_score_total>false</hide_score_total>
+            <free_form_criterion_comments>false</free_form_criterion_comments>
+            <criteria>
+              <criterion>
+                <criterion_id>9_8244</criterion_id>
+                <points>5</points>
+                <description>Example Rubric Criteria</description>
+                <ratings>
+                  <rating>
+                    <description>Full Marks</description>
+                    <points>5</points>
+                    <criterion_id>9_8244</criterion_id>
+                    <id>blank</id>
+                  </rating>
+                  <rating>
+                    <description>No Marks</description>
+                    <long_description>Missing &lt;or&gt; late</long_description>
+                    <points>0</points>
+                    <criterion_id>9_8244</criterion_id>
+                    <id>9_5394</id>
+                  </rating>
+                </ratings>
+              </criterion>
+              <criterion>
+                <criterion_id>9_268</criterion_id>
+                <points>5</points>
+                <mastery_points>4</mastery_points>
+                <ignore_for_scoring>false</ignore_for_scoring>
+                <description>Outcome, with a comma</description>
+                <long_description>&lt;p&gt;Lorem ipsum&lt;/p&gt;</long_description>
+                <learning_outcome_identifierref>i69aed9fc9ce7e3161c8225fa85a328b4</learning_outcome_identifierref>
+                <ratings>
+                  <rating>
+                    <description>Perfection!</description>
+                    <points>5</points>
+                    <criterion_id>9_268</criterion_id>
+                    <id>9_5527</id>
+                  </rating>
+                  <rating>
+                    <description>Does Not Meet Expectations</description>
+                    <points>0</points>
+                    <criterion_id>9_268</criterion_id>
+                    <id>9_7986</id>
+                  </rating>
+                </ratings>
+              </criterion>
+            </criteria>
+          </rubric>
+        </rubrics>
+      "#
+    }
+}
@@ -14,38 +14,62 @@ pub struct Node {
 impl Node {
     pub fn new(name: OwnedName, attrs: Vec<OwnedAttribute>) -> Node {
         Node {
-            name: name,
+            name,
             attributes: attrs
         }
     }
 
     pub fn name_str(&self) -> &str {
-        &self.name.local_name.as_str()
+        self.name.local_name.as_str()
     }
 
     pub fn has_name(&self, str: &str) -> bool {
         self.name_str() == str
     }
 
+    /// The value of an unprefixed attribute. Prefixed ones, like `xml:base`
+    /// or a vendor's `bb:title`, belong to other namespaces.
     pub fn find(&self, key: &str) -> Option<String> {
         for attr in &self.attributes {
-            if attr.name.local_name.as_str() == key {
+            if attr.name.prefix.is_none() && attr.name.local_name.as_str() == key {
                 return Some(attr.value.clone());
             }
         }
         None
     }
+
+    /// Local names from the root of `stack` down, innermost last. Elements
+    /// from another namespace than the root's, like vendor extensions, show
+    /// as an empty name so nothing under them matches.
+    pub fn path(stack: &[Node]) -> Vec<&str> {
+        let namespace = stack.first().map(|root| &root.name.namespace);
+        stack
+            .iter()
+            .map(|node| if Some(&node.name.namespace) == namespace { node.name_str() } else { "" })
+            .collect()
+    }
+
+    pub fn xml_base(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attr| attr.name.prefix.as_deref() == Some("xml") && attr.name.local_name == "base")
+            .map(|attr| attr.value.as_str())
+    }
 }
 
 pub fn typestr_to_type(i_type: &str) -> ItemType {
     if i_type.is_empty() {
         return ItemType::NoType;
     }
+    if i_type.starts_with("resource/x-bb-") {
+        return blackboard_type(i_type);
+    }
     lazy_static! {
         static ref ASSIGNMENT_R: Regex = Regex::new(r"assignment|associatedcontent/imscc_xmlv1p1/learning-application-resource").unwrap();
         static ref ASSESSMENT_R: Regex = Regex::new(r"assessment|quiz").unwrap();
         static ref DISCUSSION_R: Regex = Regex::new(r"imsdt").unwrap();
         static ref WEBCONTENT_R: Regex = Regex::new(r"webcontent").unwrap();
+        static ref EXTERNAL_TOOL_R: Regex = Regex::new(r"basiclti").unwrap();
         static ref WEBLINK_R: Regex = Regex::new(r"wl").unwrap();
     }
     if ASSIGNMENT_R.is_match(i_type) {
@@ -56,6 +80,8 @@ pub fn typestr_to_type(i_type: &str) -> ItemType {
         ItemType::DiscussionTopic
     } else if WEBCONTENT_R.is_match(i_type) {
         ItemType::WebContent
+    } else if EXTERNAL_TOOL_R.is_match(i_type) {
+        ItemType::ExternalTool
     } else if WEBLINK_R.is_match(i_type) {
         ItemType::WebLink
     } else {
@@ -63,8 +89,25 @@ pub fn typestr_to_type(i_type: &str) -> ItemType {
     }
 }
 
+/// Blackboard's own content handlers, used as resource types in its
+/// extension resources.
+fn blackboard_type(i_type: &str) -> ItemType {
+    match i_type {
+        "resource/x-bb-document" | "resource/x-bb-file" | "resource/x-bb-image" | "resource/x-bb-audio" |
+        "resource/x-bb-video" | "resource/x-bb-syllabus" => ItemType::WebContent,
+        "resource/x-bb-folder" | "resource/x-bb-lesson" => ItemType::SubHeader,
+        "resource/x-bb-externallink" => ItemType::ExternalUrl,
+        "resource/x-bb-asmt-test-link" | "resource/x-bb-asmt-survey-link" => ItemType::Assessment,
+        "resource/x-bb-assignment" => ItemType::Assignment,
+        "resource/x-bb-discussionboard" | "resource/x-bb-forumlink" => ItemType::DiscussionTopic,
+        "resource/x-bb-blti-link" | "resource/x-bb-toollink" => ItemType::ExternalTool,
+        _ => ItemType::Unknown { type_string: i_type.to_string() },
+    }
+}
+
+/// Depth of module `<item>`s under the organization's root item, as in
+/// `manifest/organizations/organization/item/item`.
 pub const MODULE_DEPTH: usize = 5;
-pub const MODULE_ITEM_DEPTH: usize = 6;
 
 pub fn handle_parse<R: Read, H: ParseHandler>(buffer: R, handler: &mut H) {
     for event in EventReader::new(buffer) {
@@ -77,7 +120,7 @@ pub fn handle_parse<R: Read, H: ParseHandler>(buffer: R, handler: &mut H) {
                     XmlEvent::EndElement {name} => {
                         handler.leave(name);
                     }
-                    XmlEvent::Characters(chars) => {
+                    XmlEvent::Characters(chars) | XmlEvent::CData(chars) => {
                         handler.receive_chars(chars);
                     }
                     _ => {}
@@ -90,3 +133,8 @@ pub fn handle_parse<R: Read, H: ParseHandler>(buffer: R, handler: &mut H) {
         }
     }
 }
+
+/// Canvas writes booleans as `true`/`false` element text.
+pub fn parse_flag(value: &str) -> bool {
+    value.trim().eq_ignore_ascii_case("true")
+}
@@ -0,0 +1,43 @@
+use common::ParseHandler;
+use summarize::utils::Node;
+use xml::name::OwnedName;
+
+/// Reads the `<title>` and `<url>` of a `<webLink>`.
+pub struct WebLinkHandler {
+    pub title: String,
+    pub url: Option<String>,
+    pub stack: Vec<Node>,
+}
+
+impl WebLinkHandler {
+    pub fn new() -> WebLinkHandler {
+        WebLinkHandler {
+            title: String::new(),
+            url: None,
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn finalize(self) -> (String, Option<String>) {
+        (self.title.trim().to_string(), self.url)
+    }
+}
+
+impl ParseHandler for WebLinkHandler {
+    fn enter(&mut self, node: Node) {
+        if self.stack.len() == 1 && node.has_name("url") {
+            self.url = node.find("href");
+        }
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, _name: OwnedName) {
+        self.stack.pop();
+    }
+
+    fn receive_chars(&mut self, chars: String) {
+        if self.stack.len() == 2 && self.stack[1].has_name("title") {
+            self.title.push_str(&chars);
+        }
+    }
+}
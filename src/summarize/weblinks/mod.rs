@@ -0,0 +1,37 @@
+mod handler;
+
+use std::io::Read;
+use summarize::utils::handle_parse;
+use summarize::weblinks::handler::WebLinkHandler;
+
+/// A web link resource's document, `imswl_xmlv1p0` or later.
+#[derive(Debug, PartialEq)]
+pub struct WebLink {
+    pub title: String,
+    pub url: Option<String>,
+}
+
+pub fn parse<R: Read>(weblink: R) -> WebLink {
+    let mut handler = WebLinkHandler::new();
+    handle_parse(weblink, &mut handler);
+    let (title, url) = handler.finalize();
+    WebLink { title, url }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_web_links() {
+        let link = parse(r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <webLink xmlns="http://www.imsglobal.org/xsd/imswl_v1p0">
+              <title>Botanical Society of America</title>
+              <url href="https://botany.org/" target="_blank"/>
+            </webLink>
+        "#.as_bytes());
+        assert_eq!(link.title, "Botanical Society of America");
+        assert_eq!(link.url, Some("https://botany.org/".to_string()));
+    }
+}